@@ -11,7 +11,7 @@ fetchAndInstantiate("./markdown.wasm", {})
         Module.translate = function(str) {
             let buf = newString(Module, str);
             console.log(new Uint8Array(Module.memory.buffer, buf, 10));
-            let outptr = mod.exports.translate(buf);
+            let outptr = mod.exports.translate_with_sourcepos(buf);
             console.log(new Uint8Array(Module.memory.buffer, outptr, 10));
 
             let result = copyCStr(Module, outptr);
@@ -24,6 +24,25 @@ function myTranslate(text) {
         = Module.translate(text)
 }
 
+// Scroll the preview to the block rendered from the first visible line of
+// the textarea, using the data-sourcepos attributes.
+function syncScroll(textarea) {
+    const display = document.getElementById('display');
+    const lineHeight = parseFloat(getComputedStyle(textarea).lineHeight) || 16;
+    const line = Math.floor(textarea.scrollTop / lineHeight) + 1;
+
+    let target = null;
+    for (const element of display.querySelectorAll('[data-sourcepos]')) {
+        const startLine = parseInt(element.dataset.sourcepos.split(':')[0], 10);
+        if (startLine > line) {
+            break;
+        }
+        target = element;
+    }
+
+    display.scrollTop = target ? target.offsetTop - display.offsetTop : 0;
+}
+
 
 //link: https://www.hellorust.com/demos/import-memory/index.html
 function fetchAndInstantiate(url, importObject) {
@@ -1,5 +1,9 @@
 use super::test::Bencher;
-use html_translator::top;
+use html_translator;
+
+fn top(input: &str) -> String {
+    html_translator::top(input).unwrap()
+}
 
 #[bench]
 fn bench_example_13(b: &mut Bencher) {
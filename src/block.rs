@@ -1,44 +1,98 @@
+use inline::Inline;
 use std::fmt;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum BlockType {
     Document,
     ThematicBreaks,
     BreakLine,
-    AtxHeading1,
-    AtxHeading2,
-    AtxHeading3,
-    AtxHeading4,
-    AtxHeading5,
-    AtxHeading6,
-    SetextHeadingUnderline1,
-    SetextHeadingUnderline2,
+    /// An ATX (`# foo`) or setext (`foo` underlined with `=` or `-`) heading.
+    Heading {
+        level: u8,
+        setext: bool,
+    },
     IndentedCodeBlock,
-    FencedCodeBlock,
+    /// `fence` is the opening fence, e.g. "```" or "~~~~".
+    FencedCodeBlock {
+        info: String,
+        fence: String,
+    },
     BlockQuote,
     Paragraph,
-    BulletListItem,
-    OrderedListItem,
-    LinkDefinition,
-    ReferenceLink,
+    /// Raw HTML, passed through as is unless rendering in safe mode.
+    HtmlBlock,
+    /// Consecutive bullet list items with the same marker. The list is tight
+    /// unless its items are separated by blank lines or contain blocks
+    /// separated by blank lines.
+    BulletList {
+        marker: char,
+        tight: bool,
+    },
+    /// Consecutive ordered list items with the same delimiter.
+    OrderedList {
+        start: u64,
+        delimiter: char,
+        tight: bool,
+    },
+    /// `checked` is set for GFM task list items.
+    BulletListItem {
+        marker: char,
+        checked: Option<bool>,
+    },
+    OrderedListItem {
+        number: u64,
+        delimiter: char,
+    },
+    LinkDefinition {
+        label: String,
+        destination: String,
+        title: String,
+    },
 }
 
-#[derive(Debug, PartialEq)]
+/// A place in the source document.
+///
+/// `line` and `column` are 1-based, and `column` counts bytes like cmark's
+/// sourcepos. `offset` is the 0-based byte offset.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Point {
+    pub line: usize,
+    pub column: usize,
+    pub offset: usize,
+}
+
+/// The source range of a block. `end` points just past the last byte.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Position {
+    pub start: Point,
+    pub end: Point,
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct Block {
     pub block_type: BlockType,
     pub children: Vec<Block>,
     pub raw_text: String,
     pub is_closed: bool,
+    pub position: Position,
+    /// The inline content of a leaf block, filled in by the inline parser.
+    pub inlines: Vec<Inline>,
 }
 
 impl Block {
-    pub fn add(&mut self, block_type: BlockType, text: String) {
-        let child = Block {
+    pub fn new(block_type: BlockType, text: String, position: Position) -> Block {
+        Block {
             is_closed: false,
-            block_type: block_type,
+            block_type,
             raw_text: text,
             children: vec![],
-        };
+            position,
+            inlines: vec![],
+        }
+    }
+
+    pub fn add(&mut self, block_type: BlockType, text: String) {
+        let child = Block::new(block_type, text, Position::default());
 
         self.children.push(child);
     }
@@ -59,6 +113,10 @@ impl Block {
         self.raw_text.push_str(s);
     }
 
+    pub fn extend_position(&mut self, end: Point) {
+        self.position.end = end;
+    }
+
     pub fn change_block_type(&mut self, bt: BlockType) {
         self.block_type = bt;
     }
@@ -93,6 +151,8 @@ fn test_get_mut_last_open_block() {
         block_type: BlockType::Document,
         raw_text: "".to_string(),
         children: vec![],
+        position: Position::default(),
+        inlines: vec![],
     };
 
     assert_eq!(
@@ -105,6 +165,8 @@ fn test_get_mut_last_open_block() {
         block_type: BlockType::Paragraph,
         raw_text: "foo".to_string(),
         children: vec![],
+        position: Position::default(),
+        inlines: vec![],
     };
     root_block.add_block(child1);
 
@@ -115,6 +177,8 @@ fn test_get_mut_last_open_block() {
         block_type: BlockType::Paragraph,
         raw_text: "bar".to_string(),
         children: vec![],
+        position: Position::default(),
+        inlines: vec![],
     };
     root_block.add_block(child2);
 
@@ -135,6 +199,8 @@ fn test_get_mut_prev() {
         block_type: BlockType::Document,
         raw_text: "".to_string(),
         children: vec![],
+        position: Position::default(),
+        inlines: vec![],
     };
 
     assert_eq!(None, root_block.get_mut_prev());
@@ -146,6 +212,8 @@ fn test_get_mut_prev() {
         block_type: BlockType::Paragraph,
         raw_text: "aaa".to_string(),
         children: vec![],
+        position: Position::default(),
+        inlines: vec![],
     };
 
     assert_eq!(Some(&mut expected_block), root_block.get_mut_prev());
@@ -158,11 +226,13 @@ fn test_close() {
         block_type: BlockType::Document,
         raw_text: "aaa".to_string(),
         children: vec![],
+        position: Position::default(),
+        inlines: vec![],
     };
 
     root_block.close();
 
-    assert_eq!(true, root_block.is_closed);
+    assert!(root_block.is_closed);
 }
 
 #[test]
@@ -172,6 +242,8 @@ fn test_push_raw_text() {
         block_type: BlockType::Document,
         raw_text: "aaa".to_string(),
         children: vec![],
+        position: Position::default(),
+        inlines: vec![],
     };
 
     root_block.push_raw_text("bbb");
@@ -0,0 +1,224 @@
+use super::line::{is_blank, unescape};
+use block::BlockType;
+
+// Labels longer than this many characters are not labels.
+const MAX_LABEL_LEN: usize = 999;
+
+// Destinations with parentheses nested deeper than this are not
+// destinations, as in cmark. This also keeps a paragraph full of `](` from
+// being scanned to its end for each of them.
+const MAX_PAREN_DEPTH: usize = 32;
+
+fn is_space(c: char) -> bool {
+    c == ' ' || c == '\t'
+}
+
+fn is_escaped(bytes: &[u8], i: usize) -> bool {
+    bytes[i] == b'\\' && bytes.get(i + 1).is_some_and(|c| c.is_ascii_punctuation())
+}
+
+// Skips spaces, tabs and at most one line ending.
+pub fn skip_spaces(text: &str, mut pos: usize) -> usize {
+    pos += text[pos..].len() - text[pos..].trim_start_matches(is_space).len();
+    if text[pos..].starts_with('\n') {
+        pos += 1;
+        pos += text[pos..].len() - text[pos..].trim_start_matches(is_space).len();
+    }
+    pos
+}
+
+fn line_end(text: &str, pos: usize) -> usize {
+    text[pos..].find('\n').map_or(text.len(), |i| pos + i)
+}
+
+// The end of a link label starting at `pos`, after its closing `]`. A label
+// may span lines but must not be blank or contain unescaped brackets.
+pub fn link_label(text: &str, pos: usize) -> Option<usize> {
+    let bytes = text.as_bytes();
+    if bytes.get(pos) != Some(&b'[') {
+        return None;
+    }
+    let mut i = pos + 1;
+    while i < bytes.len() {
+        match bytes[i] {
+            _ if is_escaped(bytes, i) => i += 2,
+            b'[' => return None,
+            b']' => {
+                let label = &text[pos + 1..i];
+                if label.trim().is_empty() || label.chars().count() > MAX_LABEL_LEN {
+                    return None;
+                }
+                return Some(i + 1);
+            }
+            _ => i += 1,
+        }
+    }
+    None
+}
+
+// The end of a link destination starting at `pos` and its text: either
+// anything in angle brackets on one line, or non-space characters with
+// balanced parentheses.
+pub fn link_destination(text: &str, pos: usize) -> Option<(usize, &str)> {
+    let bytes = text.as_bytes();
+    if bytes.get(pos) == Some(&b'<') {
+        let mut i = pos + 1;
+        while i < bytes.len() {
+            match bytes[i] {
+                _ if is_escaped(bytes, i) => i += 2,
+                b'>' => return Some((i + 1, &text[pos + 1..i])),
+                b'\n' | b'<' => return None,
+                _ => i += 1,
+            }
+        }
+        return None;
+    }
+
+    let mut depth = 0;
+    let mut i = pos;
+    while i < bytes.len() {
+        match bytes[i] {
+            _ if is_escaped(bytes, i) => i += 2,
+            b'(' if depth == MAX_PAREN_DEPTH => return None,
+            b'(' => {
+                depth += 1;
+                i += 1;
+            }
+            b')' if depth > 0 => {
+                depth -= 1;
+                i += 1;
+            }
+            c if c == b')' || c == b' ' || c.is_ascii_control() => break,
+            _ => i += 1,
+        }
+    }
+    if i == pos || depth > 0 {
+        return None;
+    }
+    Some((i, &text[pos..i]))
+}
+
+// The end of a link title starting at `pos` and its text, in double quotes,
+// single quotes or parentheses.
+pub fn link_title(text: &str, pos: usize) -> Option<(usize, &str)> {
+    let bytes = text.as_bytes();
+    let close = match bytes.get(pos) {
+        Some(&b'"') => b'"',
+        Some(&b'\'') => b'\'',
+        Some(&b'(') => b')',
+        _ => return None,
+    };
+    let mut i = pos + 1;
+    while i < bytes.len() {
+        match bytes[i] {
+            _ if is_escaped(bytes, i) => i += 2,
+            c if c == close => return Some((i + 1, &text[pos + 1..i])),
+            b'(' if close == b')' => return None,
+            _ => i += 1,
+        }
+    }
+    None
+}
+
+// A link reference definition at the start of the text of a paragraph, and
+// the length of the lines it takes up.
+pub fn link_definition(text: &str) -> Option<(BlockType, usize)> {
+    let label_end = link_label(text, 0)?;
+    if !text[label_end..].starts_with(':') {
+        return None;
+    }
+    let label = &text[1..label_end - 1];
+    let destination_start = skip_spaces(text, label_end + 1);
+    let (destination_end, destination) = link_destination(text, destination_start)?;
+    let block_type = |title: &str| BlockType::LinkDefinition {
+        label: label.to_string(),
+        destination: unescape(destination),
+        title: unescape(title),
+    };
+
+    // A title must be set apart by whitespace and may follow on the next
+    // line. Without a title on its own, the definition ends with the
+    // destination.
+    let title_start = skip_spaces(text, destination_end);
+    if title_start > destination_end {
+        if let Some((title_end, title)) = link_title(text, title_start) {
+            let end = line_end(text, title_end);
+            if is_blank(&text[title_end..end]) {
+                return Some((block_type(title), end));
+            }
+        }
+    }
+    let end = line_end(text, destination_end);
+    if !is_blank(&text[destination_end..end]) {
+        return None;
+    }
+    Some((block_type(""), end))
+}
+
+// The form of a label that matches all labels differing from it only in
+// case and whitespace.
+pub fn normalize_label(label: &str) -> String {
+    let words: Vec<_> = label.split_whitespace().collect();
+    // Upper casing after lower casing folds e.g. `ẞ`, `ß` and `SS` together.
+    words.join(" ").to_lowercase().to_uppercase()
+}
+
+#[test]
+fn test_link_definition() {
+    let text = "[foo]:\n/url\n'the title'\nbar";
+    let block_type = BlockType::LinkDefinition {
+        label: "foo".to_string(),
+        destination: "/url".to_string(),
+        title: "the title".to_string(),
+    };
+    assert_eq!(link_definition(text), Some((block_type, 23)));
+
+    let block_type = BlockType::LinkDefinition {
+        label: "foo".to_string(),
+        destination: "/url".to_string(),
+        title: "".to_string(),
+    };
+    assert_eq!(link_definition("[foo]: /url\nbar"), Some((block_type, 11)));
+    assert_eq!(link_definition("[foo]: /url bar"), None);
+    assert_eq!(link_definition("[foo]:\n\nbar"), None);
+
+    let block_type = BlockType::LinkDefinition {
+        label: "Foo\\]\nbar".to_string(),
+        destination: "my url".to_string(),
+        title: "a (\"b\")\nc".to_string(),
+    };
+    let text = "[Foo\\]\nbar]: <my url> \"a (\\\"b\\\")\nc\"";
+    assert_eq!(link_definition(text), Some((block_type, text.len())));
+
+    // A title followed by more text is no title.
+    let block_type = BlockType::LinkDefinition {
+        label: "foo".to_string(),
+        destination: "a(b)".to_string(),
+        title: "".to_string(),
+    };
+    assert_eq!(
+        link_definition("[foo]:a(b)\n(title) ok"),
+        Some((block_type, 10))
+    );
+    assert_eq!(link_definition("[foo]: a(b"), None);
+    assert_eq!(link_definition("[foo]: /url (ti(tle)"), None);
+}
+
+#[test]
+fn test_link_destination() {
+    let text = format!("{}{}", "(".repeat(32), ")".repeat(32));
+    assert_eq!(link_destination(&text, 0), Some((64, &text[..])));
+    let text = format!("{}{}", "(".repeat(33), ")".repeat(33));
+    assert_eq!(link_destination(&text, 0), None);
+    assert_eq!(link_destination("a\\(b c", 0), Some((4, "a\\(b")));
+}
+
+#[test]
+fn test_normalize_label() {
+    assert_eq!(
+        normalize_label(" Foo \n  bar\t"),
+        normalize_label("FOO BAR")
+    );
+    assert_eq!(normalize_label("ẞ"), normalize_label("SS"));
+    assert_ne!(normalize_label("foo"), normalize_label("foo bar"));
+}
@@ -0,0 +1,278 @@
+use super::line::is_blank;
+
+// The content of these elements may contain blank lines, so their HTML
+// blocks only end with the closing tag.
+const RAW_TAGS: [&str; 3] = ["script", "pre", "style"];
+
+const BLOCK_TAGS: [&str; 62] = [
+    "address",
+    "article",
+    "aside",
+    "base",
+    "basefont",
+    "blockquote",
+    "body",
+    "caption",
+    "center",
+    "col",
+    "colgroup",
+    "dd",
+    "details",
+    "dialog",
+    "dir",
+    "div",
+    "dl",
+    "dt",
+    "fieldset",
+    "figcaption",
+    "figure",
+    "footer",
+    "form",
+    "frame",
+    "frameset",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "head",
+    "header",
+    "hr",
+    "html",
+    "iframe",
+    "legend",
+    "li",
+    "link",
+    "main",
+    "menu",
+    "menuitem",
+    "nav",
+    "noframes",
+    "ol",
+    "optgroup",
+    "option",
+    "p",
+    "param",
+    "section",
+    "source",
+    "summary",
+    "table",
+    "tbody",
+    "td",
+    "tfoot",
+    "th",
+    "thead",
+    "title",
+    "tr",
+    "track",
+    "ul",
+];
+
+// How an HTML block ends: with the first line that contains one of the
+// strings, matched case-insensitively, or before a blank line.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HtmlBlockEnd {
+    Contains(&'static [&'static str]),
+    BlankLine,
+}
+
+impl HtmlBlockEnd {
+    pub fn is_end(&self, line: &str) -> bool {
+        match *self {
+            HtmlBlockEnd::Contains(ends) => {
+                let line = line.to_ascii_lowercase();
+                ends.iter().any(|end| line.contains(end))
+            }
+            HtmlBlockEnd::BlankLine => is_blank(line),
+        }
+    }
+}
+
+// Whether a tag name is over, e.g. `<pre` in `<pre>` or `<pre class="x">`.
+fn is_tag_name_end(rest: &str) -> bool {
+    match rest.chars().next() {
+        None => true,
+        Some(c) => c == '>' || c.is_ascii_whitespace(),
+    }
+}
+
+// The end of a tag name starting at `i`: an ASCII letter followed by ASCII
+// letters, digits and `-`.
+fn tag_name(s: &[u8], i: usize) -> Option<usize> {
+    match s.get(i) {
+        Some(c) if c.is_ascii_alphabetic() => (),
+        _ => return None,
+    }
+    let len = s[i..]
+        .iter()
+        .position(|&c| !(c.is_ascii_alphanumeric() || c == b'-'))
+        .unwrap_or(s.len() - i);
+    Some(i + len)
+}
+
+fn skip_whitespace(s: &[u8], i: usize) -> usize {
+    i + s[i..]
+        .iter()
+        .position(|c| !c.is_ascii_whitespace())
+        .unwrap_or(s.len() - i)
+}
+
+// The end of an attribute value starting at `i`.
+fn attribute_value(s: &[u8], i: usize) -> Option<usize> {
+    match s.get(i) {
+        Some(&quote) if quote == b'"' || quote == b'\'' => s[i + 1..]
+            .iter()
+            .position(|&c| c == quote)
+            .map(|len| i + 1 + len + 1),
+        _ => {
+            let len = s[i..]
+                .iter()
+                .position(|&c| c.is_ascii_whitespace() || b"\"'=<>`".contains(&c))
+                .unwrap_or(s.len() - i);
+            if len == 0 {
+                None
+            } else {
+                Some(i + len)
+            }
+        }
+    }
+}
+
+// The end of an attribute starting at `i`, after the whitespace in front of
+// it.
+fn attribute(s: &[u8], i: usize) -> Option<usize> {
+    match s.get(i) {
+        Some(&c) if c.is_ascii_alphabetic() || c == b'_' || c == b':' => (),
+        _ => return None,
+    }
+    let len = s[i..]
+        .iter()
+        .position(|&c| !(c.is_ascii_alphanumeric() || b"_.:-".contains(&c)))
+        .unwrap_or(s.len() - i);
+    let name_end = i + len;
+    let j = skip_whitespace(s, name_end);
+    if s.get(j) != Some(&b'=') {
+        return Some(name_end);
+    }
+    attribute_value(s, skip_whitespace(s, j + 1))
+}
+
+// The length of an HTML open tag at the start of `s`, e.g. `<a href="x">`.
+pub fn open_tag(s: &str) -> Option<usize> {
+    let s = s.as_bytes();
+    if s.first() != Some(&b'<') {
+        return None;
+    }
+    let mut i = tag_name(s, 1)?;
+    loop {
+        let j = skip_whitespace(s, i);
+        match attribute(s, j) {
+            Some(end) if j > i => i = end,
+            _ => {
+                i = j;
+                break;
+            }
+        }
+    }
+    if s.get(i) == Some(&b'/') {
+        i += 1;
+    }
+    if s.get(i) == Some(&b'>') {
+        Some(i + 1)
+    } else {
+        None
+    }
+}
+
+// The length of an HTML closing tag at the start of `s`, e.g. `</a>`.
+pub fn closing_tag(s: &str) -> Option<usize> {
+    let s = s.as_bytes();
+    if !s.starts_with(b"</") {
+        return None;
+    }
+    let i = skip_whitespace(s, tag_name(s, 2)?);
+    if s.get(i) == Some(&b'>') {
+        Some(i + 1)
+    } else {
+        None
+    }
+}
+
+// How the HTML block started by a line ends, if the line starts one. Only
+// a complete tag on a line of its own can not interrupt a paragraph.
+pub fn block_start(rest: &str, interrupts_paragraph: bool) -> Option<HtmlBlockEnd> {
+    if !rest.starts_with('<') {
+        return None;
+    }
+    let lower = rest.to_ascii_lowercase();
+
+    for tag in RAW_TAGS.iter() {
+        if lower[1..].starts_with(tag) && is_tag_name_end(&lower[1 + tag.len()..]) {
+            return Some(HtmlBlockEnd::Contains(&["</script>", "</pre>", "</style>"]));
+        }
+    }
+    if lower.starts_with("<!--") {
+        return Some(HtmlBlockEnd::Contains(&["-->"]));
+    }
+    if lower.starts_with("<?") {
+        return Some(HtmlBlockEnd::Contains(&["?>"]));
+    }
+    if lower.starts_with("<![cdata[") {
+        return Some(HtmlBlockEnd::Contains(&["]]>"]));
+    }
+    if rest[1..].starts_with('!') && rest[2..].starts_with(|c: char| c.is_ascii_uppercase()) {
+        return Some(HtmlBlockEnd::Contains(&[">"]));
+    }
+
+    let name_start = if lower.starts_with("</") { 2 } else { 1 };
+    let name_end = tag_name(lower.as_bytes(), name_start).unwrap_or(name_start);
+    let name = &lower[name_start..name_end];
+    let after = &lower[name_end..];
+    if BLOCK_TAGS.contains(&name) && (is_tag_name_end(after) || after.starts_with("/>")) {
+        return Some(HtmlBlockEnd::BlankLine);
+    }
+
+    if interrupts_paragraph || RAW_TAGS.contains(&name) {
+        return None;
+    }
+    match open_tag(rest).or_else(|| closing_tag(rest)) {
+        Some(len) if is_blank(&rest[len..]) => Some(HtmlBlockEnd::BlankLine),
+        _ => None,
+    }
+}
+
+#[test]
+fn test_tags() {
+    assert_eq!(
+        open_tag("<a href=\"foo\" title='bar' data-x=1 hidden/>x"),
+        Some(43)
+    );
+    assert_eq!(open_tag("<a  >"), Some(5));
+    assert_eq!(open_tag("<a href=>"), None);
+    assert_eq!(open_tag("<1a>"), None);
+    assert_eq!(closing_tag("</div >"), Some(7));
+    assert_eq!(closing_tag("</div foo>"), None);
+}
+
+#[test]
+fn test_block_start() {
+    let raw = HtmlBlockEnd::Contains(&["</script>", "</pre>", "</style>"]);
+    assert_eq!(block_start("<PRE class=\"x\">", true), Some(raw));
+    assert_eq!(block_start("<prefix>", true), None);
+    assert_eq!(
+        block_start("<!-- foo", true),
+        Some(HtmlBlockEnd::Contains(&["-->"]))
+    );
+    assert_eq!(
+        block_start("<!DOCTYPE html>", true),
+        Some(HtmlBlockEnd::Contains(&[">"]))
+    );
+    assert_eq!(block_start("</DIV>", true), Some(HtmlBlockEnd::BlankLine));
+    assert_eq!(
+        block_start("<custom-tag>", false),
+        Some(HtmlBlockEnd::BlankLine)
+    );
+    assert_eq!(block_start("<custom-tag>", true), None);
+    assert_eq!(block_start("<a href=\"foo\">bar", false), None);
+}
@@ -0,0 +1,239 @@
+use block::BlockType;
+use entity::entity;
+
+// Helpers that look at the start of a single line, after the markers of the
+// containers it continues have been taken off.
+
+pub fn is_blank(line: &str) -> bool {
+    line.bytes().all(|c| c == b' ' || c == b'\t')
+}
+
+// Tabs are not expanded in the text, but count as spaces up to the next
+// multiple of this column where indentation matters.
+pub const TAB_STOP: usize = 4;
+
+// The columns taken up by the spaces and tabs at the start of a line whose
+// first character is at `column`.
+pub fn indent(line: &str, column: usize) -> usize {
+    let mut end = column;
+    for c in line.bytes() {
+        match c {
+            b' ' => end += 1,
+            b'\t' => end += TAB_STOP - end % TAB_STOP,
+            _ => break,
+        }
+    }
+    end - column
+}
+
+// The fence character and length of an opening code fence, and its info
+// string.
+pub fn opening_fence(rest: &str) -> Option<(char, usize, &str)> {
+    let c = rest.chars().next()?;
+    if c != '`' && c != '~' {
+        return None;
+    }
+    let len = rest.len() - rest.trim_start_matches(c).len();
+    let info = &rest[len..];
+    if len < 3 || (c == '`' && info.contains('`')) {
+        return None;
+    }
+    Some((c, len, info))
+}
+
+pub fn is_closing_fence(rest: &str, c: char, len: usize) -> bool {
+    let fence = rest.len() - rest.trim_start_matches(c).len();
+    fence >= len && is_blank(&rest[fence..])
+}
+
+// The level and content of an ATX heading, without the optional closing
+// sequence of `#`s.
+pub fn atx_heading(rest: &str) -> Option<(u8, &str)> {
+    let level = rest.len() - rest.trim_start_matches('#').len();
+    if level == 0 || level > 6 {
+        return None;
+    }
+    let content = &rest[level..];
+    match content.chars().next() {
+        None | Some(' ') | Some('\t') => (),
+        _ => return None,
+    }
+    let content = content.trim_matches(|c| c == ' ' || c == '\t');
+    let without_closing = content.trim_end_matches('#');
+    let content = if without_closing.is_empty() {
+        without_closing
+    } else if without_closing.ends_with(' ') || without_closing.ends_with('\t') {
+        without_closing.trim_end_matches([' ', '\t'])
+    } else {
+        content
+    };
+    Some((level as u8, content))
+}
+
+pub fn is_thematic_break(rest: &str) -> bool {
+    scan_thematic_break(rest).is_ok()
+}
+
+// Checks for a thematic break, or returns where the check failed. No
+// thematic break can start before that point either, as all that comes
+// before it are spaces and the one marker character.
+pub fn scan_thematic_break(rest: &str) -> Result<(), usize> {
+    let c = match rest.bytes().next() {
+        Some(c) if c == b'*' || c == b'-' || c == b'_' => c,
+        _ => return Err(0),
+    };
+    let mut count = 0;
+    for (i, d) in rest.bytes().enumerate() {
+        if d == c {
+            count += 1;
+        } else if d != b' ' && d != b'\t' {
+            return Err(i);
+        }
+    }
+    if count >= 3 {
+        Ok(())
+    } else {
+        Err(rest.len())
+    }
+}
+
+// The heading level of a setext heading underline.
+pub fn setext_underline(rest: &str) -> Option<u8> {
+    let trimmed = rest.trim_end_matches([' ', '\t']);
+    if trimmed.is_empty() {
+        None
+    } else if trimmed.chars().all(|c| c == '=') {
+        Some(1)
+    } else if trimmed.chars().all(|c| c == '-') {
+        Some(2)
+    } else {
+        None
+    }
+}
+
+// A list item marker at `column`: the item type, the length of the marker
+// and the columns of the spaces after it that belong to it, unless those
+// start indented code. A thematic break takes precedence over a marker,
+// which the caller checks first.
+pub fn list_item_marker(
+    rest: &str,
+    column: usize,
+    interrupts_paragraph: bool,
+) -> Option<(BlockType, usize, usize)> {
+    let digits = rest.len() - rest.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    let (block_type, len) = match rest[digits..].chars().next() {
+        Some(marker) if digits == 0 && (marker == '-' || marker == '+' || marker == '*') => (
+            BlockType::BulletListItem {
+                marker,
+                checked: None,
+            },
+            1,
+        ),
+        Some(delimiter) if digits > 0 && digits < 10 && (delimiter == '.' || delimiter == ')') => {
+            let number = rest[..digits].parse().unwrap_or_default();
+            // Only a list starting at 1 can interrupt a paragraph.
+            if interrupts_paragraph && number != 1 {
+                return None;
+            }
+            (BlockType::OrderedListItem { number, delimiter }, digits + 1)
+        }
+        _ => return None,
+    };
+
+    let after = &rest[len..];
+    if is_blank(after) {
+        // An empty list item can not interrupt a paragraph.
+        if interrupts_paragraph {
+            return None;
+        }
+        return Some((block_type, len, 1));
+    }
+    match indent(after, column + len) {
+        0 => None,
+        spaces if spaces <= 4 => Some((block_type, len, spaces)),
+        _ => Some((block_type, len, 1)),
+    }
+}
+
+// Removes backslash escapes of ASCII punctuation and decodes entities, e.g.
+// in info strings and link destinations.
+pub fn unescape(s: &str) -> String {
+    let mut result = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(c) = rest.chars().next() {
+        let len = if c == '\\' && rest[1..].starts_with(|c: char| c.is_ascii_punctuation()) {
+            result.push_str(&rest[1..2]);
+            2
+        } else if let Some((decoded, len)) = entity(rest) {
+            result.push_str(&decoded);
+            len
+        } else {
+            result.push(c);
+            c.len_utf8()
+        };
+        rest = &rest[len..];
+    }
+    result
+}
+
+#[test]
+fn test_atx_heading() {
+    assert_eq!(atx_heading("# foo"), Some((1, "foo")));
+    assert_eq!(atx_heading("### foo ###   "), Some((3, "foo")));
+    assert_eq!(atx_heading("# foo#"), Some((1, "foo#")));
+    assert_eq!(atx_heading("## ###"), Some((2, "")));
+    assert_eq!(atx_heading("#"), Some((1, "")));
+    assert_eq!(atx_heading("#5 bolt"), None);
+    assert_eq!(atx_heading("####### foo"), None);
+}
+
+#[test]
+fn test_list_item_marker() {
+    let bullet = BlockType::BulletListItem {
+        marker: '-',
+        checked: None,
+    };
+    assert_eq!(
+        list_item_marker("- foo", 0, false),
+        Some((bullet.clone(), 1, 1))
+    );
+    assert_eq!(
+        list_item_marker("-      foo", 0, false),
+        Some((bullet.clone(), 1, 1))
+    );
+    assert_eq!(
+        list_item_marker("-\tfoo", 0, false),
+        Some((bullet.clone(), 1, 3))
+    );
+    assert_eq!(
+        list_item_marker("-\tfoo", 2, false),
+        Some((bullet.clone(), 1, 1))
+    );
+    assert_eq!(list_item_marker("-", 0, false), Some((bullet, 1, 1)));
+    assert_eq!(list_item_marker("-", 0, true), None);
+    assert_eq!(list_item_marker("2. foo", 0, true), None);
+    assert_eq!(list_item_marker("-foo", 0, false), None);
+}
+
+#[test]
+fn test_scan_thematic_break() {
+    assert_eq!(scan_thematic_break("- - -"), Ok(()));
+    assert_eq!(scan_thematic_break("- - - a"), Err(6));
+    assert_eq!(scan_thematic_break("- -"), Err(3));
+    assert_eq!(scan_thematic_break("+ + +"), Err(0));
+}
+
+#[test]
+fn test_indent() {
+    assert_eq!(indent("  foo", 0), 2);
+    assert_eq!(indent("\tfoo", 0), 4);
+    assert_eq!(indent(" \t foo", 0), 5);
+    assert_eq!(indent("\tfoo", 1), 3);
+    assert_eq!(indent("foo", 0), 0);
+}
+
+#[test]
+fn test_unescape() {
+    assert_eq!(unescape("a\\*b\\c"), "a*b\\c");
+    assert_eq!(unescape("&auml; &#35;&#x41; &amp"), "ä #A &amp");
+}
@@ -0,0 +1,161 @@
+use block::{Block, BlockType};
+use std::mem;
+
+// The list type an item belongs to, with its start number if ordered.
+// Bullet items need the same marker and ordered items the same delimiter.
+fn list_type(item: &Block) -> Option<BlockType> {
+    match item.block_type {
+        BlockType::BulletListItem { marker, .. } => Some(BlockType::BulletList {
+            marker,
+            tight: true,
+        }),
+        BlockType::OrderedListItem { number, delimiter } => Some(BlockType::OrderedList {
+            start: number,
+            delimiter,
+            tight: true,
+        }),
+        _ => None,
+    }
+}
+
+fn is_same_list(list: &BlockType, item: &BlockType) -> bool {
+    match (list, item) {
+        (
+            &BlockType::BulletList { marker, .. },
+            &BlockType::BulletListItem {
+                marker: item_marker,
+                ..
+            },
+        ) => marker == item_marker,
+        (
+            &BlockType::OrderedList { delimiter, .. },
+            &BlockType::OrderedListItem {
+                delimiter: item_delimiter,
+                ..
+            },
+        ) => delimiter == item_delimiter,
+        _ => false,
+    }
+}
+
+fn is_list(block_type: &BlockType) -> bool {
+    matches!(
+        *block_type,
+        BlockType::BulletList { .. } | BlockType::OrderedList { .. }
+    )
+}
+
+fn set_tight(list: &mut Block, is_loose: bool) {
+    match list.block_type {
+        BlockType::BulletList { ref mut tight, .. }
+        | BlockType::OrderedList { ref mut tight, .. } => *tight = !is_loose,
+        _ => (),
+    }
+}
+
+// Where a block has blank lines, as far as lists care.
+#[derive(Clone, Copy, Default)]
+struct Blanks {
+    // Blank lines are kept by the deepest open block, so a blank line after
+    // a nested list ends up in its last item.
+    at_end: bool,
+    // Whether two of its children have a blank line between them.
+    between: bool,
+}
+
+// Wraps runs of sibling list items of the same type in list blocks, and
+// marks each list tight or loose.
+pub fn group_list_items(block: &mut Block) {
+    group(block);
+}
+
+// Groups the list items of a block and its children bottom-up, so that
+// finding out where the blank lines are takes one pass over the tree.
+fn group(block: &mut Block) -> Blanks {
+    let children = mem::take(&mut block.children);
+    // Whether each grouped child ends with a blank line.
+    let mut at_end = Vec::with_capacity(children.len());
+    // A list is loose if its items are separated by blank lines, or if an
+    // item has two blocks with a blank line between them.
+    let mut is_loose = false;
+    // Blank lines after a list, which go to its last item if another item
+    // of the list follows, e.g. after an empty item.
+    let mut blank_lines = vec![];
+    for mut child in children {
+        let is_list_open = block
+            .children
+            .last()
+            .is_some_and(|last| is_list(&last.block_type));
+        if is_list_open && child.block_type == BlockType::BreakLine {
+            blank_lines.push(child);
+            continue;
+        }
+
+        let blanks = group(&mut child);
+        match block.children.last_mut() {
+            Some(ref mut list) if is_same_list(&list.block_type, &child.block_type) => {
+                let item_at_end = at_end.pop().unwrap_or(false);
+                is_loose = is_loose || item_at_end || !blank_lines.is_empty() || blanks.between;
+                if let Some(item) = list.children.last_mut() {
+                    item.children.append(&mut blank_lines);
+                }
+                list.extend_position(child.position.end);
+                list.children.push(child);
+                at_end.push(blanks.at_end);
+                continue;
+            }
+            Some(ref mut list) => set_tight(list, is_loose),
+            None => (),
+        }
+        for blank_line in blank_lines.drain(..) {
+            block.children.push(blank_line);
+            at_end.push(false);
+        }
+
+        at_end.push(blanks.at_end);
+        match list_type(&child) {
+            Some(list_type) => {
+                is_loose = blanks.between;
+                let mut list = Block::new(list_type, "".to_string(), child.position);
+                list.children.push(child);
+                block.children.push(list);
+            }
+            None => block.children.push(child),
+        }
+    }
+    if let Some(list) = block.children.last_mut() {
+        set_tight(list, is_loose);
+    }
+    for blank_line in blank_lines {
+        block.children.push(blank_line);
+        at_end.push(false);
+    }
+
+    let mut has_content = false;
+    let mut has_blank = false;
+    let mut between = false;
+    for (child, &child_at_end) in block.children.iter().zip(at_end.iter()) {
+        if child.block_type == BlockType::BreakLine {
+            has_blank = has_content;
+            continue;
+        }
+        if has_blank {
+            between = true;
+            break;
+        }
+        has_content = true;
+        has_blank = child_at_end;
+    }
+    let at_end = match block.children.last() {
+        Some(last) => match last.block_type {
+            BlockType::BreakLine => true,
+            BlockType::BulletList { .. }
+            | BlockType::OrderedList { .. }
+            | BlockType::BulletListItem { .. }
+            | BlockType::OrderedListItem { .. } => at_end.last().cloned().unwrap_or(false),
+            _ => false,
+        },
+        None => false,
+    };
+    Blanks { at_end, between }
+}
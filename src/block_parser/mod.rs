@@ -1,9 +1,327 @@
 use block::Block;
+use block::BlockType;
+use error::Result;
+use options::ParseOptions;
 
+mod definition;
+mod html;
+mod line;
+mod list;
 mod parser;
-mod tree;
 
-pub fn top(lines: &str) -> Block {
-    let pest_tree = parser::parse(lines);
-    tree::to_tree(pest_tree)
+pub use self::definition::{
+    link_destination, link_label, link_title, normalize_label, skip_spaces,
+};
+pub use self::line::unescape;
+
+// Parsing blocks can not fail, as any line that starts no other block is
+// paragraph text.
+pub fn top(input_str: &str, options: &ParseOptions) -> Result<Block> {
+    let mut root_block = parser::parse(input_str);
+    list::group_list_items(&mut root_block);
+    if options.tasklist {
+        mark_task_list_items(&mut root_block);
+    }
+    Ok(root_block)
+}
+
+// Turns bullet list items starting with `[ ]`, `[x]` or `[X]` into task list
+// items and removes the marker from their first paragraph.
+fn mark_task_list_items(block: &mut Block) {
+    for child in block.children.iter_mut() {
+        mark_task_list_items(child);
+    }
+
+    let marker = match block.block_type {
+        BlockType::BulletListItem { marker, .. } => marker,
+        _ => return,
+    };
+
+    let checked = match block.children.first() {
+        Some(paragraph) if paragraph.block_type == BlockType::Paragraph => {
+            let text = paragraph.get_text();
+            if text.starts_with("[ ] ") {
+                false
+            } else if text.starts_with("[x] ") || text.starts_with("[X] ") {
+                true
+            } else {
+                return;
+            }
+        }
+        _ => return,
+    };
+
+    block.change_block_type(BlockType::BulletListItem {
+        marker,
+        checked: Some(checked),
+    });
+    let paragraph = &mut block.children[0];
+    paragraph.raw_text = paragraph.raw_text[4..].to_string();
+    paragraph.position.start.column += 4;
+    paragraph.position.start.offset += 4;
+}
+
+#[test]
+fn test_block_positions() {
+    use block::{BlockType, Point};
+
+    let document = top("# foo\n\n> bar\n> baz", &ParseOptions::default()).unwrap();
+    assert_eq!(
+        document.position.start,
+        Point {
+            line: 1,
+            column: 1,
+            offset: 0
+        }
+    );
+    assert_eq!(
+        document.position.end,
+        Point {
+            line: 4,
+            column: 6,
+            offset: 18
+        }
+    );
+
+    let heading = &document.children[0];
+    assert_eq!(
+        heading.block_type,
+        BlockType::Heading {
+            level: 1,
+            setext: false,
+        }
+    );
+    assert_eq!(
+        heading.position.start,
+        Point {
+            line: 1,
+            column: 1,
+            offset: 0
+        }
+    );
+    assert_eq!(
+        heading.position.end,
+        Point {
+            line: 1,
+            column: 6,
+            offset: 5
+        }
+    );
+
+    let block_quote = &document.children[2];
+    assert_eq!(block_quote.block_type, BlockType::BlockQuote);
+    assert_eq!(
+        block_quote.position.start,
+        Point {
+            line: 3,
+            column: 1,
+            offset: 7
+        }
+    );
+    assert_eq!(
+        block_quote.position.end,
+        Point {
+            line: 4,
+            column: 6,
+            offset: 18
+        }
+    );
+
+    let paragraph = &block_quote.children[0];
+    assert_eq!(
+        paragraph.position.start,
+        Point {
+            line: 3,
+            column: 3,
+            offset: 9
+        }
+    );
+    assert_eq!(
+        paragraph.position.end,
+        Point {
+            line: 4,
+            column: 6,
+            offset: 18
+        }
+    );
+}
+
+#[test]
+fn test_task_list_items() {
+    let options = ParseOptions {
+        tasklist: true,
+        ..ParseOptions::default()
+    };
+
+    let document = top("- [x] foo", &options).unwrap();
+    let item = &document.children[0].children[0];
+    assert_eq!(
+        item.block_type,
+        BlockType::BulletListItem {
+            marker: '-',
+            checked: Some(true),
+        }
+    );
+    assert_eq!(item.children[0].get_text(), "foo");
+
+    let document = top("- [ ] foo", &ParseOptions::default()).unwrap();
+    let item = &document.children[0].children[0];
+    assert_eq!(
+        item.block_type,
+        BlockType::BulletListItem {
+            marker: '-',
+            checked: None,
+        }
+    );
+    assert_eq!(item.children[0].get_text(), "[ ] foo");
+}
+
+#[test]
+fn test_block_type_data() {
+    let input =
+        "foo\n---\n\n## bar\n\n3) baz\n\n* qux\n\n~~~~ rust\ncode\n~~~~\n\n[a]: /url 'title'";
+    let document = top(input, &ParseOptions::default()).unwrap();
+    let block_types: Vec<_> = document
+        .children
+        .iter()
+        .map(|child| &child.block_type)
+        .filter(|block_type| **block_type != BlockType::BreakLine)
+        .cloned()
+        .collect();
+    assert_eq!(
+        block_types,
+        vec![
+            BlockType::Heading {
+                level: 2,
+                setext: true,
+            },
+            BlockType::Heading {
+                level: 2,
+                setext: false,
+            },
+            BlockType::OrderedList {
+                start: 3,
+                delimiter: ')',
+                tight: true,
+            },
+            BlockType::BulletList {
+                marker: '*',
+                tight: true,
+            },
+            BlockType::FencedCodeBlock {
+                info: "rust".to_string(),
+                fence: "~~~~".to_string(),
+            },
+            BlockType::LinkDefinition {
+                label: "a".to_string(),
+                destination: "/url".to_string(),
+                title: "title".to_string(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_list_grouping() {
+    let document = top(
+        "- a\n- b\n+ c\n\n+ d\n\n1. e\n1) f",
+        &ParseOptions::default(),
+    )
+    .unwrap();
+    let lists: Vec<_> = document
+        .children
+        .iter()
+        .map(|list| (list.block_type.clone(), list.children.len()))
+        .collect();
+    assert_eq!(
+        lists,
+        vec![
+            (
+                BlockType::BulletList {
+                    marker: '-',
+                    tight: true,
+                },
+                2
+            ),
+            (
+                BlockType::BulletList {
+                    marker: '+',
+                    tight: false,
+                },
+                2
+            ),
+            (
+                BlockType::OrderedList {
+                    start: 1,
+                    delimiter: '.',
+                    tight: true,
+                },
+                1
+            ),
+            (
+                BlockType::OrderedList {
+                    start: 1,
+                    delimiter: ')',
+                    tight: true,
+                },
+                1
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_list_after_empty_item() {
+    let document = top("1. a\n2.\n\n3. c", &ParseOptions::default()).unwrap();
+    assert_eq!(document.children.len(), 1);
+    let list = &document.children[0];
+    assert_eq!(
+        list.block_type,
+        BlockType::OrderedList {
+            start: 1,
+            delimiter: '.',
+            tight: false,
+        }
+    );
+    assert_eq!(list.children.len(), 3);
+}
+
+#[test]
+fn test_nested_list_items() {
+    let document = top("- a\n  1. b\n\n     > c\n- d", &ParseOptions::default()).unwrap();
+    let list = &document.children[0];
+    assert_eq!(list.children.len(), 2);
+
+    let item = &list.children[0];
+    assert_eq!(item.children[0].block_type, BlockType::Paragraph);
+    let inner_list = &item.children[1];
+    assert_eq!(
+        inner_list.block_type,
+        BlockType::OrderedList {
+            start: 1,
+            delimiter: '.',
+            tight: false,
+        }
+    );
+    let inner_item = &inner_list.children[0];
+    assert_eq!(inner_item.children[0].get_text(), "b");
+    let block_quote = inner_item
+        .children
+        .iter()
+        .find(|child| child.block_type == BlockType::BlockQuote)
+        .unwrap();
+    assert_eq!(block_quote.position.start.line, 4);
+    assert_eq!(block_quote.position.start.column, 6);
+}
+
+#[test]
+fn test_lazy_continuation() {
+    let document = top("> - foo\nbar\n===", &ParseOptions::default()).unwrap();
+    let block_quote = &document.children[0];
+    assert_eq!(block_quote.position.end.line, 3);
+
+    let item = &block_quote.children[0].children[0];
+    assert_eq!(item.children.len(), 1);
+    assert_eq!(item.children[0].block_type, BlockType::Paragraph);
+    assert_eq!(item.children[0].get_text(), "foo\nbar\n===");
 }
@@ -1,251 +1,886 @@
-use pest::iterators::Pairs;
-use pest::Parser;
+use super::definition;
+use super::html::{self, HtmlBlockEnd};
+use super::line::{self, indent, is_blank, TAB_STOP};
+use block::{Block, BlockType, Point, Position};
+use std::borrow::Cow;
 
-#[derive(Parser)]
-#[grammar = "block_parser/block.pest"]
-struct BlockParser;
+// Byte offsets of the line starts, so that offsets can be turned into
+// line/column pairs without rescanning the input for every block.
+struct LineIndex {
+    line_starts: Vec<usize>,
+}
+
+impl LineIndex {
+    fn new(input: &str) -> LineIndex {
+        let bytes = input.as_bytes();
+        let mut line_starts = vec![0];
+        for (i, &b) in bytes.iter().enumerate() {
+            let is_line_ending =
+                b == b'\n' || (b == b'\r' && bytes.get(i + 1).is_none_or(|&c| c != b'\n'));
+            if is_line_ending {
+                line_starts.push(i + 1);
+            }
+        }
+        LineIndex { line_starts }
+    }
+
+    fn point(&self, offset: usize) -> Point {
+        let line = match self.line_starts.binary_search(&offset) {
+            Ok(line) => line,
+            Err(line) => line - 1,
+        };
+        Point {
+            line: line + 1,
+            column: offset - self.line_starts[line] + 1,
+            offset,
+        }
+    }
+
+    fn position(&self, start: usize, end: usize) -> Position {
+        Position {
+            start: self.point(start),
+            end: self.point(end),
+        }
+    }
+}
+
+// What is left of a line once the markers of the containers it continues
+// have been taken off. Indentation is measured in columns, and a tab that
+// was only partly taken off leaves its other columns as spaces in front of
+// the text.
+#[derive(Clone, Copy)]
+struct Rest<'a> {
+    text: &'a str,
+    offset: usize,
+    column: usize,
+    spaces: usize,
+}
+
+impl<'a> Rest<'a> {
+    fn new(text: &'a str, offset: usize) -> Rest<'a> {
+        Rest {
+            text,
+            offset,
+            column: 0,
+            spaces: 0,
+        }
+    }
+
+    fn indent(&self) -> usize {
+        self.spaces + indent(self.text, self.column + self.spaces)
+    }
+
+    fn is_blank(&self) -> bool {
+        is_blank(self.text)
+    }
+
+    fn end(&self) -> usize {
+        self.offset + self.text.len()
+    }
+
+    // Takes up to `columns` columns of spaces and tabs off the start.
+    fn skip_columns(&mut self, mut columns: usize) {
+        let spaces = self.spaces.min(columns);
+        self.spaces -= spaces;
+        self.column += spaces;
+        columns -= spaces;
+        while columns > 0 {
+            let width = match self.text.as_bytes().first() {
+                Some(&b' ') => 1,
+                Some(&b'\t') => TAB_STOP - self.column % TAB_STOP,
+                _ => break,
+            };
+            self.text = &self.text[1..];
+            self.offset += 1;
+            if width > columns {
+                self.spaces = width - columns;
+            }
+            let width = width.min(columns);
+            self.column += width;
+            columns -= width;
+        }
+    }
+
+    // Takes all of the indentation off and returns its columns.
+    fn skip_indent(&mut self) -> usize {
+        let columns = self.indent();
+        self.skip_columns(columns);
+        columns
+    }
+
+    // Takes `len` bytes of markers off the start.
+    fn skip(&mut self, len: usize) {
+        self.text = &self.text[len..];
+        self.offset += len;
+        self.column += self.spaces + len;
+        self.spaces = 0;
+    }
+
+    // Takes a block quote marker and the optional space after it off the
+    // start, after at most three columns of indentation.
+    fn skip_block_quote_marker(&mut self) -> bool {
+        let mut rest = *self;
+        if rest.skip_indent() >= 4 || !rest.text.starts_with('>') {
+            return false;
+        }
+        rest.skip(1);
+        rest.skip_columns(1);
+        *self = rest;
+        true
+    }
+
+    // The text with what is left of a partly taken off tab.
+    fn content(&self) -> Cow<'a, str> {
+        if self.spaces == 0 {
+            Cow::Borrowed(self.text)
+        } else {
+            Cow::Owned(" ".repeat(self.spaces) + self.text)
+        }
+    }
+}
+
+// Block quotes and list items nested deeper than this are paragraph text,
+// as the passes over the block tree recurse into every container.
+const MAX_NESTING: usize = 100;
+
+// An open container block: the document, a block quote or a list item.
+struct Container {
+    block: Block,
+    // The content width of a list item; continuation lines must be indented
+    // at least this far. Block quotes and the document have none.
+    width: Option<usize>,
+    // The end of the last line with content.
+    end: usize,
+}
 
-pub fn parse(line: &str) -> Pairs<Rule> {
-    BlockParser::parse(Rule::document, line).unwrap_or_else(|e| panic!("{}", e))
+// The leaf block at the tip of the open blocks, which the next line may
+// continue. Each line is kept with its offset in the document.
+enum Leaf<'a> {
+    // The lines of a paragraph without their indentation.
+    Paragraph(Vec<(usize, &'a str)>),
+    IndentedCode {
+        lines: Vec<Rest<'a>>,
+        // Blank lines only belong to the code if more code follows.
+        blank_lines: Vec<Rest<'a>>,
+    },
+    FencedCode {
+        start: usize,
+        end: usize,
+        indent: usize,
+        fence: &'a str,
+        info: &'a str,
+        text: String,
+    },
+    // The lines of an HTML block, kept as they are.
+    Html {
+        lines: Vec<Rest<'a>>,
+        end: HtmlBlockEnd,
+    },
+}
+
+// The block structure of a document, built line by line as in the first
+// phase of the CommonMark parsing strategy: every line either continues the
+// open blocks, or closes some of them and opens new ones.
+struct Parser<'a> {
+    index: LineIndex,
+    containers: Vec<Container>,
+    leaf: Option<Leaf<'a>>,
+    // The offset before which no thematic break starts on the current line,
+    // so that nested list markers do not rescan the rest of the line.
+    thematic_break_end: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(input: &str) -> Parser<'a> {
+        let index = LineIndex::new(input);
+        let document = Block::new(BlockType::Document, "".to_string(), index.position(0, 0));
+        Parser {
+            index,
+            containers: vec![Container {
+                block: document,
+                width: None,
+                end: 0,
+            }],
+            leaf: None,
+            thematic_break_end: 0,
+        }
+    }
+
+    fn is_paragraph_open(&self) -> bool {
+        matches!(self.leaf, Some(Leaf::Paragraph(_)))
+    }
+
+    // A list item marker at the start of `rest`, unless it is part of a
+    // thematic break.
+    fn list_item_marker(
+        &mut self,
+        rest: &Rest<'a>,
+        interrupts_paragraph: bool,
+    ) -> Option<(BlockType, usize, usize)> {
+        if rest.offset >= self.thematic_break_end {
+            match line::scan_thematic_break(rest.text) {
+                Ok(()) => return None,
+                Err(len) => self.thematic_break_end = rest.offset + len,
+            }
+        }
+        line::list_item_marker(rest.text, rest.column, interrupts_paragraph)
+    }
+
+    fn add_line(&mut self, mut rest: Rest<'a>) {
+        // Match the open containers against the start of the line.
+        let mut matched = 1;
+        while matched < self.containers.len() {
+            let is_tip = matched + 1 == self.containers.len();
+            let container = &self.containers[matched];
+            match container.width {
+                None => {
+                    if !rest.skip_block_quote_marker() {
+                        break;
+                    }
+                }
+                // A list item can start with at most one blank line.
+                Some(_) if rest.is_blank() => {
+                    if is_tip && self.leaf.is_none() && container.block.children.is_empty() {
+                        break;
+                    }
+                    let len = rest.text.len();
+                    rest.skip(len);
+                }
+                Some(width) if rest.indent() >= width => rest.skip_columns(width),
+                _ => break,
+            }
+            matched += 1;
+        }
+
+        let all_matched = matched == self.containers.len();
+        if all_matched {
+            match self.leaf {
+                Some(Leaf::FencedCode { .. }) => return self.add_fenced_code_line(rest),
+                Some(Leaf::Html { end, .. }) if end != HtmlBlockEnd::BlankLine || !rest.is_blank() => {
+                    return self.add_html_line(rest)
+                }
+                _ => (),
+            }
+        }
+        let interrupts_paragraph = all_matched && self.is_paragraph_open();
+
+        // Open new containers.
+        let mut opened = false;
+        while rest.indent() < 4 {
+            // The containers left open once the unmatched ones are closed.
+            let depth = if opened { self.containers.len() } else { matched };
+            if depth > MAX_NESTING {
+                break;
+            }
+            let mut next = rest;
+            let spaces = next.skip_indent();
+            let marker_start = next.offset;
+            let (block_type, width) = if next.skip_block_quote_marker() {
+                (BlockType::BlockQuote, None)
+            } else if let Some((block_type, len, padding)) =
+                self.list_item_marker(&next, interrupts_paragraph && !opened)
+            {
+                next.skip(len);
+                next.skip_columns(padding);
+                (block_type, Some(spaces + len + padding))
+            } else {
+                break;
+            };
+            if !opened {
+                self.close_containers(matched);
+                opened = true;
+            }
+            self.close_leaf();
+            rest = next;
+            let position = self.index.position(marker_start, marker_start);
+            self.containers.push(Container {
+                block: Block::new(block_type, "".to_string(), position),
+                width,
+                end: rest.offset,
+            });
+            if width.is_some() && rest.is_blank() {
+                // The item starts with a blank line.
+                return;
+            }
+        }
+
+        // A lazy continuation line continues a paragraph in a container it
+        // does not match.
+        if !opened && !all_matched && self.is_paragraph_open() && !rest.is_blank() {
+            let mut content = rest;
+            let spaces = content.skip_indent();
+            let interrupts = spaces < 4
+                && (line::is_thematic_break(content.text)
+                    || line::atx_heading(content.text).is_some()
+                    || line::opening_fence(content.text).is_some()
+                    || html::block_start(content.text, true).is_some());
+            if !interrupts {
+                if let Some(Leaf::Paragraph(ref mut lines)) = self.leaf {
+                    lines.push((content.offset, content.text));
+                }
+                let end = rest.end();
+                for container in self.containers.iter_mut() {
+                    container.end = end;
+                }
+                return;
+            }
+        }
+
+        if !opened {
+            self.close_containers(matched);
+        }
+        self.add_leaf_line(rest);
+    }
+
+    // Adds a line to the leaf blocks of the innermost open container.
+    fn add_leaf_line(&mut self, rest: Rest<'a>) {
+        if rest.is_blank() {
+            if let Some(Leaf::IndentedCode {
+                ref mut blank_lines,
+                ..
+            }) = self.leaf
+            {
+                blank_lines.push(rest);
+                return;
+            }
+            self.close_leaf();
+            self.add_break_line(rest.offset, rest.text);
+            return;
+        }
+
+        let end = rest.end();
+        self.set_end(end);
+
+        let mut content = rest;
+        let spaces = content.skip_indent();
+        let start = content.offset;
+        let text = content.text;
+        if spaces >= 4 {
+            // Indented code can not interrupt a paragraph.
+            match self.leaf {
+                Some(Leaf::Paragraph(ref mut lines)) => lines.push((start, text)),
+                Some(Leaf::IndentedCode {
+                    ref mut lines,
+                    ref mut blank_lines,
+                }) => {
+                    lines.append(blank_lines);
+                    lines.push(rest);
+                }
+                _ => {
+                    self.close_leaf();
+                    self.leaf = Some(Leaf::IndentedCode {
+                        lines: vec![rest],
+                        blank_lines: vec![],
+                    });
+                }
+            }
+            return;
+        }
+
+        if let Some((_, len, info)) = line::opening_fence(text) {
+            self.close_leaf();
+            self.leaf = Some(Leaf::FencedCode {
+                start,
+                end,
+                indent: spaces,
+                fence: &text[..len],
+                info,
+                text: String::new(),
+            });
+            return;
+        }
+
+        if let Some(html_end) = html::block_start(text, self.is_paragraph_open()) {
+            self.close_leaf();
+            self.leaf = Some(Leaf::Html {
+                lines: vec![],
+                end: html_end,
+            });
+            return self.add_html_line(rest);
+        }
+
+        if let Some((level, heading)) = line::atx_heading(text) {
+            self.close_leaf();
+            let block_type = BlockType::Heading {
+                level,
+                setext: false,
+            };
+            self.add_block(block_type, heading.to_string(), start, end);
+            return;
+        }
+
+        if self.is_paragraph_open() {
+            if let Some(level) = line::setext_underline(text) {
+                if self.add_setext_heading(level, end) {
+                    return;
+                }
+            }
+        }
+
+        if line::is_thematic_break(text) {
+            self.close_leaf();
+            self.add_block(BlockType::ThematicBreaks, "".to_string(), start, end);
+            return;
+        }
+
+        match self.leaf {
+            Some(Leaf::Paragraph(ref mut lines)) => lines.push((start, text)),
+            _ => {
+                self.close_leaf();
+                self.leaf = Some(Leaf::Paragraph(vec![(start, text)]));
+            }
+        }
+    }
+
+    fn add_fenced_code_line(&mut self, rest: Rest<'a>) {
+        let mut content = rest;
+        let spaces = content.skip_indent();
+        let mut is_closed = false;
+        if let Some(Leaf::FencedCode {
+            ref mut end,
+            indent,
+            fence,
+            ref mut text,
+            ..
+        }) = self.leaf
+        {
+            let c = fence.chars().next().unwrap_or('`');
+            if spaces < 4 && line::is_closing_fence(content.text, c, fence.len()) {
+                is_closed = true;
+            } else {
+                // Content lines lose as much indentation as the opening fence had.
+                let mut content = rest;
+                content.skip_columns(indent);
+                text.push_str(&content.content());
+                text.push('\n');
+            }
+            *end = rest.end();
+        }
+        if !rest.is_blank() {
+            self.set_end(rest.end());
+        }
+        if is_closed {
+            self.close_leaf();
+        }
+    }
+
+    fn add_html_line(&mut self, rest: Rest<'a>) {
+        let mut is_closed = false;
+        if let Some(Leaf::Html {
+            ref mut lines, end, ..
+        }) = self.leaf
+        {
+            lines.push(rest);
+            is_closed = end.is_end(rest.text);
+        }
+        if !rest.is_blank() {
+            self.set_end(rest.end());
+        }
+        if is_closed {
+            self.close_leaf();
+        }
+    }
+
+    // Turns the open paragraph into a setext heading, unless it only held
+    // link reference definitions.
+    fn add_setext_heading(&mut self, level: u8, end: usize) -> bool {
+        let lines = match self.leaf.take() {
+            Some(Leaf::Paragraph(lines)) => self.add_definitions(lines),
+            leaf => {
+                self.leaf = leaf;
+                return false;
+            }
+        };
+        let start = match lines.first() {
+            Some(&(start, _)) => start,
+            None => return false,
+        };
+        let block_type = BlockType::Heading {
+            level,
+            setext: true,
+        };
+        self.add_block(block_type, join_lines(&lines), start, end);
+        true
+    }
+
+    // Adds the link reference definitions at the start of a paragraph and
+    // returns the lines left over.
+    fn add_definitions(&mut self, mut lines: Vec<(usize, &'a str)>) -> Vec<(usize, &'a str)> {
+        let text = join_lines(&lines);
+        let mut pos = 0;
+        let mut count = 0;
+        while pos < text.len() {
+            let (block_type, len) = match definition::link_definition(&text[pos..]) {
+                Some(definition) => definition,
+                None => break,
+            };
+            let (start, _) = lines[count];
+            count += text[pos..pos + len].matches('\n').count() + 1;
+            let (last_start, last) = lines[count - 1];
+            self.add_block(block_type, "".to_string(), start, last_start + last.len());
+            // Skip the line ending after the definition.
+            pos += len + 1;
+        }
+        lines.drain(..count);
+        lines
+    }
+
+    fn close_leaf(&mut self) {
+        match self.leaf.take() {
+            None => (),
+            Some(Leaf::Paragraph(lines)) => {
+                let lines = self.add_definitions(lines);
+                let (start, _) = match lines.first() {
+                    Some(&first) => first,
+                    None => return,
+                };
+                let (last_start, last) = lines[lines.len() - 1];
+                let end = last_start + last.len();
+                self.add_block(BlockType::Paragraph, join_lines(&lines), start, end);
+            }
+            Some(Leaf::IndentedCode { lines, blank_lines }) => {
+                let start = lines[0].offset;
+                let end = lines[lines.len() - 1].end();
+                let code: Vec<_> = lines.into_iter().map(code_line).collect();
+                self.add_block(BlockType::IndentedCodeBlock, join_contents(&code), start, end);
+                for rest in blank_lines {
+                    self.add_break_line(rest.offset, rest.text);
+                }
+            }
+            Some(Leaf::FencedCode {
+                start,
+                end,
+                fence,
+                info,
+                text,
+                ..
+            }) => {
+                let block_type = BlockType::FencedCodeBlock {
+                    info: line::unescape(info.trim()),
+                    fence: fence.to_string(),
+                };
+                self.add_block(block_type, text, start, end);
+            }
+            Some(Leaf::Html { lines, .. }) => {
+                let mut first = lines[0];
+                first.skip_indent();
+                let end = lines[lines.len() - 1].end();
+                self.add_block(BlockType::HtmlBlock, join_contents(&lines), first.offset, end);
+            }
+        }
+    }
+
+    fn add_break_line(&mut self, offset: usize, line: &str) {
+        self.add_block(BlockType::BreakLine, "".to_string(), offset, offset + line.len());
+    }
+
+    fn add_block(&mut self, block_type: BlockType, text: String, start: usize, end: usize) {
+        let mut block = Block::new(block_type, text, self.index.position(start, end));
+        block.close();
+        if let Some(container) = self.containers.last_mut() {
+            container.block.add_block(block);
+        }
+    }
+
+    fn set_end(&mut self, end: usize) {
+        if let Some(container) = self.containers.last_mut() {
+            container.end = end;
+        }
+    }
+
+    // Closes the open blocks above `depth`, adding each to its parent.
+    fn close_containers(&mut self, depth: usize) {
+        while self.containers.len() > depth {
+            self.close_leaf();
+            let container = match self.containers.pop() {
+                Some(container) => container,
+                None => break,
+            };
+            let mut block = container.block;
+            block.extend_position(self.index.point(container.end));
+            block.close();
+            if let Some(parent) = self.containers.last_mut() {
+                parent.end = parent.end.max(container.end);
+                parent.block.add_block(block);
+            }
+        }
+    }
+
+    fn finish(mut self, len: usize) -> Block {
+        self.close_containers(1);
+        self.close_leaf();
+        let mut document = self.containers.remove(0).block;
+        document.extend_position(self.index.point(len));
+        document
+    }
+}
+
+fn join_lines(lines: &[(usize, &str)]) -> String {
+    let lines: Vec<_> = lines.iter().map(|&(_, line)| line).collect();
+    lines.join("\n")
+}
+
+fn join_contents(lines: &[Rest]) -> String {
+    let lines: Vec<_> = lines.iter().map(|line| line.content()).collect();
+    lines.join("\n")
+}
+
+// A line of indented code without its indentation, which may leave part of
+// a tab.
+fn code_line(mut rest: Rest) -> Rest {
+    rest.skip_columns(4);
+    rest
+}
+
+// Splits the document into lines, which may end with "\n", "\r\n" or "\r",
+// and builds its block tree.
+pub fn parse(input: &str) -> Block {
+    let mut parser = Parser::new(input);
+    let mut start = 0;
+    while start < input.len() {
+        let len = input[start..]
+            .find(['\n', '\r'])
+            .unwrap_or(input.len() - start);
+        let line = &input[start..start + len];
+        let mut next = start + len;
+        if input[next..].starts_with("\r\n") {
+            next += 2;
+        } else if next < input.len() {
+            next += 1;
+        }
+        parser.add_line(Rest::new(line, start));
+        start = next;
+    }
+    parser.finish(input.len())
 }
 
 #[cfg(test)]
 mod tests {
-    use super::BlockParser;
-    use super::Rule;
+    use super::{parse, LineIndex, Rest, MAX_NESTING};
+    use block::{BlockType, Point};
+
+    // The types and text of the top-level blocks.
+    fn blocks(input: &str) -> Vec<(BlockType, String)> {
+        parse(input)
+            .children
+            .into_iter()
+            .map(|block| (block.block_type, block.raw_text))
+            .collect()
+    }
 
     #[test]
-    fn test_parsing_themantic_break() {
-        parses_to! {
-            parser: BlockParser,
-            input: "*     *      *      *\n",
-            rule: Rule::document,
-            tokens: [
-              thematic_break(0, 21, [
-              ]),
-            ]
-        };
+    fn test_line_index() {
+        let index = LineIndex::new("ab\ncd\r\nef\rg");
+        assert_eq!(
+            index.point(0),
+            Point {
+                line: 1,
+                column: 1,
+                offset: 0
+            }
+        );
+        assert_eq!(
+            index.point(2),
+            Point {
+                line: 1,
+                column: 3,
+                offset: 2
+            }
+        );
+        assert_eq!(
+            index.point(4),
+            Point {
+                line: 2,
+                column: 2,
+                offset: 4
+            }
+        );
+        assert_eq!(
+            index.point(7),
+            Point {
+                line: 3,
+                column: 1,
+                offset: 7
+            }
+        );
+        assert_eq!(
+            index.point(10),
+            Point {
+                line: 4,
+                column: 1,
+                offset: 10
+            }
+        );
     }
 
     #[test]
-    fn test_parsing_paragraph_underlines_as_setext_heading_underlines() {
-        parses_to! {
-            parser: BlockParser,
-            input: "-- a\n",
-            rule: Rule::document,
-            tokens: [
-              paragraph(0, 4, [
-              ]),
-            ]
-        };
+    fn test_rest_columns() {
+        let mut rest = Rest::new(" \t\tfoo", 0);
+        assert_eq!(rest.indent(), 8);
+        rest.skip_columns(2);
+        assert_eq!((rest.text, rest.spaces, rest.indent()), ("\tfoo", 2, 6));
+        assert_eq!(rest.content(), "  \tfoo");
+        rest.skip_columns(4);
+        assert_eq!((rest.text, rest.spaces, rest.column), ("foo", 2, 6));
+        assert_eq!(rest.content(), "  foo");
+
+        let mut rest = Rest::new(">\tfoo", 0);
+        assert!(rest.skip_block_quote_marker());
+        assert_eq!((rest.text, rest.spaces, rest.indent()), ("foo", 2, 2));
     }
 
     #[test]
-    fn test_parsing_paragraph_as_themantic_break() {
-        parses_to! {
-            parser: BlockParser,
-            input: "_ _ _ _ a\n",
-            rule: Rule::document,
-            tokens: [
-              paragraph(0, 9, [
-              ]),
-            ]
-        };
+    fn test_parsing_thematic_break() {
+        assert_eq!(
+            blocks("*     *      *      *"),
+            vec![(BlockType::ThematicBreaks, "".to_string())]
+        );
     }
 
     #[test]
-    fn test_parsing_indented_code_block() {
-        parses_to! {
-            parser: BlockParser,
-            input: "    indented code block\n",
-            rule: Rule::document,
-            tokens: [
-              indented_code_block(0, 23, [
-                text(4,23,[]),
-              ]),
-            ]
+    fn test_parsing_thematic_break_in_list_item() {
+        let document = parse("- - -\n- * * *\n- - - a");
+        let block_types: Vec<_> = document
+            .children
+            .iter()
+            .map(|block| {
+                let child = block.children.first().map(|child| child.block_type.clone());
+                (block.block_type.clone(), child)
+            })
+            .collect();
+        let bullet = |marker| BlockType::BulletListItem {
+            marker,
+            checked: None,
         };
+        assert_eq!(
+            block_types,
+            vec![
+                (BlockType::ThematicBreaks, None),
+                (bullet('-'), Some(BlockType::ThematicBreaks)),
+                (bullet('-'), Some(bullet('-'))),
+            ]
+        );
     }
 
     #[test]
-    fn test_parsing_fenced_code_block() {
-        parses_to! {
-            parser: BlockParser,
-            input: "```\naaa\n~~~\n```\n",
-            rule: Rule::document,
-            tokens: [
-              fenced_code_block(0, 15, [
-                fenced_text1(4, 12, [])
-              ]),
-            ]
-        };
+    fn test_parsing_nesting_limit() {
+        let mut block = parse(&"> ".repeat(MAX_NESTING + 2));
+        let mut depth = 0;
+        while block.block_type != BlockType::Paragraph {
+            block = block.children.remove(0);
+            depth += 1;
+        }
+        assert_eq!(depth, MAX_NESTING + 1);
+        assert_eq!(block.raw_text, "> > ");
     }
 
     #[test]
-    fn test1_parsing_atx_headings() {
-        parses_to! {
-            parser: BlockParser,
-            input: "# foo\n## foo\n### foo\n#### foo\n##### foo\n###### foo\n",
-            rule: Rule::document,
-            tokens: [
-              atx_heading1(0, 5, [
-                atx_heading_text(2,5,[]),
-              ]),
-              atx_heading2(6, 12, [
-                atx_heading_text(9,12,[]),
-              ]),
-              atx_heading3(13, 20, [
-                atx_heading_text(17,20,[]),
-              ]),
-              atx_heading4(21, 29, [
-                atx_heading_text(26,29,[]),
-              ]),
-              atx_heading5(30, 39, [
-                atx_heading_text(36,39,[]),
-              ]),
-              atx_heading6(40, 50, [
-                atx_heading_text(47,50,[]),
-              ]),
-            ]
-        };
+    fn test_parsing_paragraph_as_thematic_break() {
+        assert_eq!(
+            blocks("_ _ _ _ a"),
+            vec![(BlockType::Paragraph, "_ _ _ _ a".to_string())]
+        );
+        assert_eq!(
+            blocks("-- a"),
+            vec![(BlockType::Paragraph, "-- a".to_string())]
+        );
     }
 
     #[test]
-    fn test2_parsing_atx_headings() {
-        parses_to! {
-            parser: BlockParser,
-            input: " ### foo \n",
-            rule: Rule::document,
-            tokens: [
-              atx_heading3(1,9, [
-                atx_heading_text(5,9,[]),
-              ]),
+    fn test_parsing_indented_code_block() {
+        assert_eq!(
+            blocks("    foo\n\n      bar\n\n"),
+            vec![
+                (BlockType::IndentedCodeBlock, "foo\n\n  bar".to_string()),
+                (BlockType::BreakLine, "".to_string()),
             ]
-        };
+        );
     }
 
     #[test]
-    fn test3_parsing_atx_headings() {
-        parses_to! {
-            parser: BlockParser,
-            input: "### foo ###     \n",
-            rule: Rule::document,
-            tokens: [
-              atx_heading3(0, 16, [
-                atx_heading_text(4,8,[]),
-              ]),
-            ]
+    fn test_parsing_fenced_code_block() {
+        let fenced_code_block = BlockType::FencedCodeBlock {
+            info: "rust".to_string(),
+            fence: "````".to_string(),
         };
+        assert_eq!(
+            blocks("  ```` rust\n  aaa\n ```\n```` \nbbb"),
+            vec![
+                (fenced_code_block, "aaa\n```\n".to_string()),
+                (BlockType::Paragraph, "bbb".to_string()),
+            ]
+        );
     }
 
-//    #[test]
-//    fn test4_parsing_atx_headings() {
-//        parses_to! {
-//            parser: BlockParser,
-//            input: "### foo ### b\n",
-//            rule: Rule::document,
-//            tokens: [
-//              atx_heading3(0, 5, [
-//                atx_heading_text(2,5,[]),
-//              ]),
-//            ]
-//        };
-//  }
-
     #[test]
-    fn test_parsing_paragraph() {
-        parses_to! {
-            parser: BlockParser,
-            input: "  aaa\nbbb\n\nccc\n   d d d\n",
-            rule: Rule::document,
-            tokens: [
-              paragraph(2, 5, [
-              ]),
-              paragraph(6, 9, [
-              ]),
-              break_line(10, 10, [
-              ]),
-              paragraph(11, 14, [
-              ]),
-              paragraph(18, 23, [
-              ])
-            ]
+    fn test_parsing_atx_headings() {
+        let heading = |level| BlockType::Heading {
+            level,
+            setext: false,
         };
+        assert_eq!(
+            blocks("# foo\n ### bar ###  \n###### baz #\n# a # b"),
+            vec![
+                (heading(1), "foo".to_string()),
+                (heading(3), "bar".to_string()),
+                (heading(6), "baz".to_string()),
+                (heading(1), "a # b".to_string()),
+            ]
+        );
     }
 
     #[test]
-    fn test_parsing_setext_heading_underlines() {
-        parses_to! {
-            parser: BlockParser,
-            input: "Foo\n-------------------------\n\nFoo\n=\n",
-            rule: Rule::document,
-            tokens: [
-              paragraph(0, 3, [
-              ]),
-              setext_heading_underline2(4, 29, [
-              ]),
-              break_line(30, 30, [
-              ]),
-              paragraph(31, 34, [
-              ]),
-              setext_heading_underline1(35, 36, [
-              ])
-            ]
-        };
+    fn test_parsing_paragraph() {
+        let document = parse("  aaa\nbbb\n\nccc\n   d d d");
+        let paragraphs: Vec<_> = document
+            .children
+            .iter()
+            .map(|block| (block.raw_text.as_str(), block.position.start.offset))
+            .collect();
+        assert_eq!(
+            paragraphs,
+            vec![("aaa\nbbb", 2), ("", 10), ("ccc\nd d d", 11)]
+        );
     }
 
     #[test]
-    fn test_parsing_empty() {
-        parses_to! {
-            parser: BlockParser,
-            input: "\n",
-            rule: Rule::document,
-            tokens: [
-              break_line(0, 0, [
-              ]),
-            ]
+    fn test_parsing_setext_heading_underlines() {
+        let heading = |level| BlockType::Heading {
+            level,
+            setext: true,
         };
+        assert_eq!(
+            blocks("Foo\nbar\n---\n\nFoo\n=\n\n==="),
+            vec![
+                (heading(2), "Foo\nbar".to_string()),
+                (BlockType::BreakLine, "".to_string()),
+                (heading(1), "Foo".to_string()),
+                (BlockType::BreakLine, "".to_string()),
+                (BlockType::Paragraph, "===".to_string()),
+            ]
+        );
     }
 
     #[test]
-    fn test_parsing_block_quote() {
-        parses_to! {
-            parser: BlockParser,
-            input: "> # Foo\n> bar\n> baz\n",
-            rule: Rule::document,
-            tokens: [
-              block_quote(0, 7, [
-                atx_heading1(2, 7, [
-                   atx_heading_text(4, 7, []),
-                ])
-              ]),
-              block_quote(8, 13, [
-                paragraph(10, 13, []),
-              ]),
-              block_quote(14, 19, [
-                paragraph(16, 19, []),
-              ]),
-            ]
-        };
+    fn test_parsing_empty() {
+        assert_eq!(blocks(""), vec![]);
+        assert_eq!(blocks("\n"), vec![(BlockType::BreakLine, "".to_string())]);
     }
 
     #[test]
     fn test_parsing_link_reference_definition() {
-        parses_to! {
-            parser: BlockParser,
-            input: "   [foo]: \n      /url  \n           'the title'  \n\n[foo]\n",
-            rule: Rule::document,
-            tokens: [
-              link_definition(0, 48, [
-                link_label_text(4, 7, []),
-                link_destination_text(18, 21, []),
-                link_title_text(36, 45, [])
-              ]),
-              break_line(49, 49, []),
-              reference_link(50, 55, [
-                link_label_text(51, 54, []),
-              ]),
-            ]
+        let link_definition = BlockType::LinkDefinition {
+            label: "foo".to_string(),
+            destination: "/url".to_string(),
+            title: "the title".to_string(),
         };
+        assert_eq!(
+            blocks("   [foo]: \n      /url  \n           'the title'  \n\n[foo]"),
+            vec![
+                (link_definition, "".to_string()),
+                (BlockType::BreakLine, "".to_string()),
+                (BlockType::Paragraph, "[foo]".to_string()),
+            ]
+        );
     }
 }
@@ -0,0 +1,79 @@
+mod table;
+
+use self::table::ENTITIES;
+
+// The longest numeric references, as longer ones are not references.
+const MAX_DECIMAL_DIGITS: usize = 7;
+const MAX_HEX_DIGITS: usize = 6;
+
+// A numeric character reference decodes to its code point, or to U+FFFD
+// if that is 0 or no valid character.
+fn numeric(digits: &str, radix: u32) -> String {
+    let c = u32::from_str_radix(digits, radix)
+        .ok()
+        .filter(|&code| code != 0)
+        .and_then(::std::char::from_u32)
+        .unwrap_or('\u{fffd}');
+    c.to_string()
+}
+
+// An entity or numeric character reference at the start of `s`, e.g.
+// `&amp;`, `&#35;` or `&#x1F600;`, decoded, with its length. Names that are
+// not in the HTML5 table are no references.
+pub fn entity(s: &str) -> Option<(String, usize)> {
+    if !s.starts_with('&') {
+        return None;
+    }
+    let body = &s[1..];
+    let (prefix, max_digits, is_digit): (usize, usize, fn(&u8) -> bool) =
+        if body.starts_with("#x") || body.starts_with("#X") {
+            (2, MAX_HEX_DIGITS, |c| c.is_ascii_hexdigit())
+        } else if body.starts_with('#') {
+            (1, MAX_DECIMAL_DIGITS, |c| c.is_ascii_digit())
+        } else {
+            (0, usize::MAX, |c| c.is_ascii_alphanumeric())
+        };
+    let digits = body.as_bytes()[prefix..]
+        .iter()
+        .take_while(|c| is_digit(c))
+        .count();
+    if digits == 0 || digits > max_digits || !body[prefix + digits..].starts_with(';') {
+        return None;
+    }
+    let len = 1 + prefix + digits + 1;
+    let name = &body[prefix..prefix + digits];
+    let decoded = match prefix {
+        2 => numeric(name, 16),
+        1 => numeric(name, 10),
+        _ => {
+            let i = ENTITIES
+                .binary_search_by_key(&name, |&(name, _)| name)
+                .ok()?;
+            ENTITIES[i].1.to_string()
+        }
+    };
+    Some((decoded, len))
+}
+
+#[test]
+fn test_entity() {
+    assert_eq!(entity("&amp;"), Some(("&".to_string(), 5)));
+    assert_eq!(entity("&ngE; x"), Some(("\u{2267}\u{338}".to_string(), 5)));
+    assert_eq!(entity("&#35;&#1234;"), Some(("#".to_string(), 5)));
+    assert_eq!(entity("&#X22;"), Some(("\"".to_string(), 6)));
+    assert_eq!(entity("&#x1F600;"), Some(("\u{1f600}".to_string(), 9)));
+    assert_eq!(entity("&#0;"), Some(("\u{fffd}".to_string(), 4)));
+    assert_eq!(entity("&#xD800;"), Some(("\u{fffd}".to_string(), 8)));
+    assert_eq!(entity("&#x110000;"), Some(("\u{fffd}".to_string(), 10)));
+    assert_eq!(entity("&#98765432;"), None);
+    assert_eq!(entity("&yopf;"), Some(("\u{1d56a}".to_string(), 6)));
+    assert_eq!(entity("&MadeUpEntity;"), None);
+    assert_eq!(entity("&copy"), None);
+    assert_eq!(entity("&#;"), None);
+    assert_eq!(entity("&#x;"), None);
+}
+
+#[test]
+fn test_entity_table_is_sorted() {
+    assert!(ENTITIES.windows(2).all(|pair| pair[0].0 < pair[1].0));
+}
@@ -0,0 +1,65 @@
+use pest::error::{Error as PestError, LineColLocation};
+use pest::RuleType;
+use std::error;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Error {
+    /// The text of a block could not be split into inlines. `line` is the
+    /// line in the document, and `column` the 1-based byte column within the
+    /// text of the block on that line, which leaves out container markers
+    /// and indentation.
+    Inline { line: usize, column: usize },
+}
+
+impl Error {
+    pub fn inline<R: RuleType>(e: &PestError<R>) -> Error {
+        let (line, column) = line_col(e);
+        Error::Inline { line, column }
+    }
+
+    pub fn line(&self) -> usize {
+        match *self {
+            Error::Inline { line, .. } => line,
+        }
+    }
+
+    pub fn column(&self) -> usize {
+        match *self {
+            Error::Inline { column, .. } => column,
+        }
+    }
+}
+
+fn line_col<R: RuleType>(e: &PestError<R>) -> (usize, usize) {
+    match e.line_col {
+        LineColLocation::Pos(pos) => pos,
+        LineColLocation::Span(start, _) => start,
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::Inline { line, column } => write!(
+                f,
+                "unable to parse inline at line {}, column {}",
+                line, column
+            ),
+        }
+    }
+}
+
+impl error::Error for Error {}
+
+pub type Result<T> = ::std::result::Result<T, Error>;
+
+#[test]
+fn test_display() {
+    let error = Error::Inline { line: 2, column: 3 };
+    assert_eq!(
+        error.to_string(),
+        "unable to parse inline at line 2, column 3"
+    );
+    assert_eq!((error.line(), error.column()), (2, 3));
+}
@@ -0,0 +1,336 @@
+use block::{Block, BlockType, Position};
+use error::Result;
+use inline::Inline;
+use options::ParseOptions;
+use std::collections::VecDeque;
+use std::vec;
+
+/// An element that contains other events, opened by `Event::Start` and
+/// closed by a matching `Event::End`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Tag {
+    Paragraph,
+    /// A heading of level 1 to 6.
+    Heading(u8),
+    BlockQuote,
+    /// A fenced or indented code block with its info string, which is empty
+    /// for indented code.
+    CodeBlock(String),
+    /// A list with the start number of an ordered list.
+    List(Option<u64>),
+    Item,
+    Emphasis,
+    Strong,
+    Strikethrough,
+    Link {
+        destination: String,
+        title: String,
+    },
+    /// An image, whose description is the text of the events it contains.
+    Image {
+        destination: String,
+        title: String,
+    },
+}
+
+/// A step in a pre-order walk over a parsed document.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event {
+    Start(Tag),
+    End(Tag),
+    /// Literal text, including the content of code blocks.
+    Text(String),
+    /// The content of a code span.
+    Code(String),
+    /// Raw HTML, either a whole HTML block or inline HTML.
+    Html(String),
+    SoftBreak,
+    HardBreak,
+    /// A thematic break.
+    Rule,
+    /// The checkbox of a GFM task list item, checked or not.
+    TaskListMarker(bool),
+}
+
+// The remaining children of an open block and the events closing it.
+struct Frame {
+    children: vec::IntoIter<Block>,
+    tight: bool,
+    end: Vec<(Event, Option<Position>)>,
+}
+
+/// A pull parser yielding the events of a Markdown document.
+///
+/// `Parser::new` parses the whole document, blocks and inlines, up front
+/// and keeps the tree in memory, so that parse errors are returned before
+/// any event. The parser does not stream its input; it only produces the
+/// events of each block once the iterator reaches it.
+///
+/// ```
+/// use markdown::{Event, Parser, Tag};
+///
+/// // Drop emphasis but keep its text.
+/// let parser = Parser::new("*foo* bar").unwrap().filter(|event| match *event {
+///     Event::Start(Tag::Emphasis) | Event::End(Tag::Emphasis) => false,
+///     _ => true,
+/// });
+/// let mut html = String::new();
+/// markdown::push_html(&mut html, parser, &Default::default());
+/// assert_eq!(html, "<p>foo bar</p>");
+/// ```
+pub struct Parser {
+    stack: Vec<Frame>,
+    pending: VecDeque<(Event, Option<Position>)>,
+}
+
+impl Parser {
+    pub fn new(input: &str) -> Result<Parser> {
+        Parser::new_with_options(input, &ParseOptions::default())
+    }
+
+    pub fn new_with_options(input: &str, options: &ParseOptions) -> Result<Parser> {
+        Ok(Parser::from_block(::parse_with_options(input, options)?))
+    }
+
+    /// Walks a block tree such as the one returned by `markdown::parse`.
+    pub fn from_block(document: Block) -> Parser {
+        let mut parser = Parser {
+            stack: vec![],
+            pending: VecDeque::new(),
+        };
+        parser.push_block(document, false);
+        parser
+    }
+
+    /// Yields the position of each event along with it.
+    ///
+    /// Only block-level events have a position.
+    pub fn into_offset_iter(self) -> OffsetIter {
+        OffsetIter { parser: self }
+    }
+
+    fn next_with_position(&mut self) -> Option<(Event, Option<Position>)> {
+        loop {
+            if let Some(event) = self.pending.pop_front() {
+                return Some(event);
+            }
+            let frame = self.stack.last_mut()?;
+            let (child, tight) = (frame.children.next(), frame.tight);
+            match child {
+                Some(block) => self.push_block(block, tight),
+                None => {
+                    if let Some(frame) = self.stack.pop() {
+                        self.pending.extend(frame.end);
+                    }
+                }
+            }
+        }
+    }
+
+    // Queues the events of a block, deferring those of its children.
+    fn push_block(&mut self, block: Block, tight: bool) {
+        let pos = Some(block.position);
+        match block.block_type {
+            BlockType::Document => self.push_children(block.children, false, vec![]),
+            BlockType::ThematicBreaks => self.pending.push_back((Event::Rule, pos)),
+            BlockType::BreakLine | BlockType::LinkDefinition { .. } => (),
+            BlockType::HtmlBlock => self.pending.push_back((Event::Html(block.raw_text), pos)),
+            BlockType::Paragraph if tight => self.push_inlines(block.inlines),
+            BlockType::Paragraph => {
+                self.pending.push_back((Event::Start(Tag::Paragraph), pos));
+                self.push_inlines(block.inlines);
+                self.pending.push_back((Event::End(Tag::Paragraph), pos));
+            }
+            BlockType::Heading { level, .. } => {
+                self.pending
+                    .push_back((Event::Start(Tag::Heading(level)), pos));
+                self.push_inlines(block.inlines);
+                self.pending
+                    .push_back((Event::End(Tag::Heading(level)), pos));
+            }
+            BlockType::IndentedCodeBlock => {
+                self.push_code_block("".to_string(), block.raw_text, pos)
+            }
+            BlockType::FencedCodeBlock { info, .. } => {
+                self.push_code_block(info, block.raw_text, pos)
+            }
+            BlockType::BlockQuote => {
+                self.pending.push_back((Event::Start(Tag::BlockQuote), pos));
+                let end = vec![(Event::End(Tag::BlockQuote), pos)];
+                self.push_children(block.children, false, end);
+            }
+            BlockType::BulletList { tight, .. } => {
+                self.push_list(Tag::List(None), tight, block.children, pos)
+            }
+            BlockType::OrderedList { start, tight, .. } => {
+                self.push_list(Tag::List(Some(start)), tight, block.children, pos)
+            }
+            BlockType::BulletListItem { checked, .. } => {
+                self.pending.push_back((Event::Start(Tag::Item), pos));
+                if let Some(checked) = checked {
+                    self.pending
+                        .push_back((Event::TaskListMarker(checked), None));
+                }
+                let end = vec![(Event::End(Tag::Item), pos)];
+                self.push_children(block.children, tight, end);
+            }
+            BlockType::OrderedListItem { .. } => {
+                self.pending.push_back((Event::Start(Tag::Item), pos));
+                let end = vec![(Event::End(Tag::Item), pos)];
+                self.push_children(block.children, tight, end);
+            }
+        }
+    }
+
+    fn push_children(
+        &mut self,
+        children: Vec<Block>,
+        tight: bool,
+        end: Vec<(Event, Option<Position>)>,
+    ) {
+        self.stack.push(Frame {
+            children: children.into_iter(),
+            tight,
+            end,
+        });
+    }
+
+    // Items pass the tightness of their list on to their paragraphs.
+    fn push_list(&mut self, tag: Tag, tight: bool, items: Vec<Block>, pos: Option<Position>) {
+        self.pending.push_back((Event::Start(tag.clone()), pos));
+        self.push_children(items, tight, vec![(Event::End(tag), pos)]);
+    }
+
+    fn push_code_block(&mut self, info: String, text: String, pos: Option<Position>) {
+        let tag = Tag::CodeBlock(info);
+        self.pending.push_back((Event::Start(tag.clone()), pos));
+        self.pending.push_back((Event::Text(text), None));
+        self.pending.push_back((Event::End(tag), pos));
+    }
+
+    fn push_inlines(&mut self, inlines: Vec<Inline>) {
+        for inline in inlines {
+            self.push_inline(inline);
+        }
+    }
+
+    fn push_inline(&mut self, inline: Inline) {
+        let (tag, children) = match inline {
+            Inline::Text(s) => return self.pending.push_back((Event::Text(s), None)),
+            Inline::Code(s) => return self.pending.push_back((Event::Code(s), None)),
+            Inline::Html(s) => return self.pending.push_back((Event::Html(s), None)),
+            Inline::HardBreak => return self.pending.push_back((Event::HardBreak, None)),
+            Inline::SoftBreak => return self.pending.push_back((Event::SoftBreak, None)),
+            Inline::Emphasis(children) => (Tag::Emphasis, children),
+            Inline::Strong(children) => (Tag::Strong, children),
+            Inline::Strikethrough(children) => (Tag::Strikethrough, children),
+            Inline::Link {
+                destination,
+                title,
+                children,
+            } => (Tag::Link { destination, title }, children),
+            Inline::Image {
+                destination,
+                title,
+                children,
+            } => (Tag::Image { destination, title }, children),
+        };
+        self.pending.push_back((Event::Start(tag.clone()), None));
+        self.push_inlines(children);
+        self.pending.push_back((Event::End(tag), None));
+    }
+}
+
+impl Iterator for Parser {
+    type Item = Event;
+
+    fn next(&mut self) -> Option<Event> {
+        self.next_with_position().map(|(event, _)| event)
+    }
+}
+
+/// The iterator returned by `Parser::into_offset_iter`.
+pub struct OffsetIter {
+    parser: Parser,
+}
+
+impl Iterator for OffsetIter {
+    type Item = (Event, Option<Position>);
+
+    fn next(&mut self) -> Option<(Event, Option<Position>)> {
+        self.parser.next_with_position()
+    }
+}
+
+#[test]
+fn test_events() {
+    let events: Vec<Event> = Parser::new("# *foo*\n\n- bar\n\n> ***").unwrap().collect();
+    assert_eq!(
+        events,
+        vec![
+            Event::Start(Tag::Heading(1)),
+            Event::Start(Tag::Emphasis),
+            Event::Text("foo".to_string()),
+            Event::End(Tag::Emphasis),
+            Event::End(Tag::Heading(1)),
+            Event::Start(Tag::List(None)),
+            Event::Start(Tag::Item),
+            Event::Text("bar".to_string()),
+            Event::End(Tag::Item),
+            Event::End(Tag::List(None)),
+            Event::Start(Tag::BlockQuote),
+            Event::Rule,
+            Event::End(Tag::BlockQuote),
+        ]
+    );
+}
+
+#[test]
+fn test_events_with_options() {
+    // The definition comes after the reference, and inlines are parsed with
+    // the options given.
+    let options = ParseOptions {
+        strikethrough: true,
+        ..ParseOptions::default()
+    };
+    let events: Vec<Event> = Parser::new_with_options("~~[foo]~~\n\n[foo]: /url", &options)
+        .unwrap()
+        .collect();
+    let link = Tag::Link {
+        destination: "/url".to_string(),
+        title: "".to_string(),
+    };
+    assert_eq!(
+        events,
+        vec![
+            Event::Start(Tag::Paragraph),
+            Event::Start(Tag::Strikethrough),
+            Event::Start(link.clone()),
+            Event::Text("foo".to_string()),
+            Event::End(link),
+            Event::End(Tag::Strikethrough),
+            Event::End(Tag::Paragraph),
+        ]
+    );
+}
+
+#[test]
+fn test_reference_link_events() {
+    let events: Vec<Event> = Parser::new("[foo]: /url 'title'\n\n[foo]")
+        .unwrap()
+        .collect();
+    let link = Tag::Link {
+        destination: "/url".to_string(),
+        title: "title".to_string(),
+    };
+    assert_eq!(
+        events,
+        vec![
+            Event::Start(Tag::Paragraph),
+            Event::Start(link.clone()),
+            Event::Text("foo".to_string()),
+            Event::End(link),
+            Event::End(Tag::Paragraph),
+        ]
+    );
+}
@@ -1,164 +1,647 @@
-use block::Block;
+#[cfg(test)]
 use block::BlockType;
-use block_parser;
-use htmlescape::encode_minimal;
-use inline_parser;
-use std::collections::HashMap;
-
-fn print(tree: Block, mut env: &mut HashMap<String, String>) -> String {
-    match tree {
-        Block {
-            block_type: BlockType::Document,
-            children,
-            ..
-        } => {
-            let mut result_str = String::new();
-            for v in children {
-                result_str.push_str(&print(v, &mut env))
+use block::{Block, Position};
+use error::Result;
+use event::{Event, Parser, Tag};
+#[cfg(test)]
+use inline::Inline;
+use options::{ParseOptions, RenderOptions};
+use std::fmt::{self, Write};
+use std::io;
+
+// Writes the attribute for a block, nothing unless sourcepos is enabled.
+// The end column is inclusive, unlike `Position::end`.
+fn write_sourcepos<W: Write>(out: &mut W, position: &Position) -> fmt::Result {
+    let start = position.start;
+    let end = position.end;
+    let end_column = if end.offset > start.offset && end.column > 1 {
+        end.column - 1
+    } else {
+        end.column
+    };
+    write!(
+        out,
+        " data-sourcepos=\"{}:{}-{}:{}\"",
+        start.line, start.column, end.line, end_column
+    )
+}
+
+// Escapes text for use in element content and attribute values.
+pub fn escape_html<W: Write>(out: &mut W, s: &str) -> fmt::Result {
+    let mut written = 0;
+    for (i, c) in s.char_indices() {
+        let escaped = match c {
+            '&' => "&amp;",
+            '<' => "&lt;",
+            '>' => "&gt;",
+            '"' => "&quot;",
+            _ => continue,
+        };
+        out.write_str(&s[written..i])?;
+        out.write_str(escaped)?;
+        written = i + 1;
+    }
+    out.write_str(&s[written..])
+}
+
+// Escapes a link destination for use in an attribute, percent-encoding
+// what may not appear in a URL as cmark does.
+fn escape_href<W: Write>(out: &mut W, s: &str) -> fmt::Result {
+    for &c in s.as_bytes() {
+        match c {
+            b'&' => out.write_str("&amp;")?,
+            b'\'' => out.write_str("&#x27;")?,
+            _ if c.is_ascii_alphanumeric() || b"-_.!~*();/?:@=+$,%#".contains(&c) => {
+                out.write_char(c as char)?
             }
-            result_str
+            _ => write!(out, "%{:02X}", c)?,
         }
-        Block {
-            block_type: BlockType::ThematicBreaks,
-            ..
-        } => {
-            let mut result_str = String::with_capacity(6);
-            result_str.push_str("<hr />");
-            result_str
-        }
-        Block {
-            block_type: BlockType::BreakLine,
-            ..
-        } => "".to_string(),
-        Block {
-            block_type: BlockType::Paragraph,
-            raw_text,
-            ..
-        } => format!("<p>{}</p>", raw_text),
-        Block {
-            block_type: BlockType::AtxHeading1,
-            raw_text,
-            ..
-        } => format!("<h1>{}</h1>", raw_text),
-        Block {
-            block_type: BlockType::AtxHeading2,
-            raw_text,
-            ..
-        } => format!("<h2>{}</h2>", raw_text),
-        Block {
-            block_type: BlockType::AtxHeading3,
-            raw_text,
-            ..
-        } => format!("<h3>{}</h3>", raw_text),
-        Block {
-            block_type: BlockType::AtxHeading4,
-            raw_text,
-            ..
-        } => format!("<h4>{}</h4>", raw_text),
-        Block {
-            block_type: BlockType::AtxHeading5,
-            raw_text,
-            ..
-        } => format!("<h5>{}</h5>", raw_text),
-        Block {
-            block_type: BlockType::AtxHeading6,
-            raw_text,
-            ..
-        } => format!("<h6>{}</h6>", raw_text),
-        Block {
-            block_type: BlockType::SetextHeadingUnderline1,
-            raw_text,
-            ..
-        } => format!("<h1>{}</h1>", raw_text),
-        Block {
-            block_type: BlockType::SetextHeadingUnderline2,
-            raw_text,
-            ..
-        } => format!("<h2>{}</h2>", raw_text),
-        Block {
-            block_type: BlockType::IndentedCodeBlock,
-            raw_text,
-            ..
-        } => format!("<pre><code>{}</code></pre>", encode_minimal(&raw_text)),
-        Block {
-            block_type: BlockType::FencedCodeBlock,
-            raw_text,
-            ..
-        } => format!("<pre><code>{}</code></pre>", encode_minimal(&raw_text)),
-        Block {
-            block_type: BlockType::BlockQuote,
-            children,
-            ..
-        } => {
-            let mut result_str = String::new();
-            for v in children {
-                result_str.push_str(&print(v, &mut env))
+    }
+    Ok(())
+}
+
+// Whether a destination runs script or reads local files, which safe mode
+// leaves out as cmark does. Only data URLs of common image types are let
+// through.
+fn is_dangerous_url(url: &str) -> bool {
+    let starts_with = |prefix: &str| {
+        url.get(..prefix.len())
+            .is_some_and(|start| start.eq_ignore_ascii_case(prefix))
+    };
+    if starts_with("data:") {
+        return !["png", "gif", "jpeg", "webp"]
+            .iter()
+            .any(|image| starts_with(&format!("data:image/{}", image)));
+    }
+    starts_with("javascript:") || starts_with("vbscript:") || starts_with("file:")
+}
+
+fn write_title<W: Write>(out: &mut W, title: &str) -> fmt::Result {
+    if title.is_empty() {
+        return Ok(());
+    }
+    out.write_str(" title=\"")?;
+    escape_html(out, title)?;
+    out.write_str("\"")
+}
+
+struct HtmlWriter<'a, I, W: 'a> {
+    events: I,
+    out: &'a mut W,
+    options: &'a RenderOptions,
+}
+
+impl<'a, I, W> HtmlWriter<'a, I, W>
+where
+    I: Iterator<Item = (Event, Option<Position>)>,
+    W: Write,
+{
+    fn run(&mut self) -> fmt::Result {
+        while let Some((event, position)) = self.events.next() {
+            let position = if self.options.sourcepos {
+                position
+            } else {
+                None
+            };
+            match event {
+                Event::Start(tag) => self.start_tag(tag, position)?,
+                Event::End(tag) => self.end_tag(tag)?,
+                Event::Text(s) => escape_html(self.out, &s)?,
+                Event::Code(s) => {
+                    self.out.write_str("<code>")?;
+                    escape_html(self.out, &s)?;
+                    self.out.write_str("</code>")?;
+                }
+                Event::Html(s) => {
+                    if self.options.safe {
+                        escape_html(self.out, &s)?
+                    } else {
+                        self.out.write_str(&s)?
+                    }
+                }
+                Event::SoftBreak => {
+                    if self.options.hard_breaks {
+                        write!(self.out, "<br{}", self.options.void_end())?
+                    } else {
+                        self.out.write_str("\n")?
+                    }
+                }
+                Event::HardBreak => write!(self.out, "<br{}", self.options.void_end())?,
+                Event::Rule => {
+                    self.out.write_str("<hr")?;
+                    self.write_position(position)?;
+                    self.out.write_str(self.options.void_end())?;
+                }
+                Event::TaskListMarker(checked) => write!(
+                    self.out,
+                    "<input{} disabled=\"\" type=\"checkbox\"{} ",
+                    if checked { " checked=\"\"" } else { "" },
+                    self.options.void_end()
+                )?,
             }
-            format!("<blockquote>{}</blockquote>", result_str)
         }
-        Block {
-            block_type: BlockType::BulletListItem,
-            children,
-            ..
-        } => {
-            if children.len() <= 2 {
-                //  Paragraph + BreakLine | Paragraph
-                return format!(
-                    "<ul><li>{}</li></ul>",
-                    children.iter().next().unwrap().get_text()
-                );
+        Ok(())
+    }
+
+    fn write_position(&mut self, position: Option<Position>) -> fmt::Result {
+        match position {
+            Some(position) => write_sourcepos(self.out, &position),
+            None => Ok(()),
+        }
+    }
+
+    // Writes an element start tag, with the attributes of `position` for
+    // blocks.
+    fn open(&mut self, name: &str, position: Option<Position>) -> fmt::Result {
+        write!(self.out, "<{}", name)?;
+        self.write_position(position)?;
+        self.out.write_str(">")
+    }
+
+    // Writes a link or image destination, left empty in safe mode when it
+    // is dangerous.
+    fn write_href(&mut self, destination: &str) -> fmt::Result {
+        if self.options.safe && is_dangerous_url(destination) {
+            return Ok(());
+        }
+        escape_href(self.out, destination)
+    }
+
+    fn start_tag(&mut self, tag: Tag, position: Option<Position>) -> fmt::Result {
+        match tag {
+            Tag::Paragraph => self.open("p", position),
+            Tag::Heading(level) => {
+                write!(self.out, "<h{}", self.options.heading_level(level))?;
+                self.write_position(position)?;
+                self.out.write_str(">")
             }
-            let mut result_str = String::new();
-            // ad_hoc
-            for v in children {
-                result_str.push_str(&print(v, &mut env))
+            Tag::BlockQuote => self.open("blockquote", position),
+            Tag::CodeBlock(info) => {
+                self.open("pre", position)?;
+                // Only the first word of the info string names the language.
+                match info.split_whitespace().next() {
+                    Some(language) => {
+                        self.out.write_str("<code class=\"language-")?;
+                        escape_html(self.out, language)?;
+                        self.out.write_str("\">")
+                    }
+                    None => self.out.write_str("<code>"),
+                }
             }
-            format!("<ul><li>{}</li></ul>", result_str)
-        }
-        Block {
-            block_type: BlockType::OrderedListItem,
-            children,
-            ..
-        } => {
-            let mut result_str = String::new();
-            for v in children {
-                result_str.push_str(&print(v, &mut env))
+            Tag::List(None) => self.open("ul", position),
+            Tag::List(Some(start)) => {
+                self.out.write_str("<ol")?;
+                self.write_position(position)?;
+                if start != 1 {
+                    write!(self.out, " start=\"{}\"", start)?;
+                }
+                self.out.write_str(">")
             }
-            format!("<ol><li>{}</li></ol>", result_str)
+            Tag::Item => self.open("li", position),
+            Tag::Emphasis => self.out.write_str("<em>"),
+            Tag::Strong => self.out.write_str("<strong>"),
+            Tag::Strikethrough => self.out.write_str("<del>"),
+            Tag::Link { destination, title } => {
+                self.out.write_str("<a href=\"")?;
+                self.write_href(&destination)?;
+                self.out.write_str("\"")?;
+                write_title(self.out, &title)?;
+                self.out.write_str(">")
+            }
+            Tag::Image { destination, title } => {
+                let alt = self.alt_text();
+                self.out.write_str("<img src=\"")?;
+                self.write_href(&destination)?;
+                self.out.write_str("\" alt=\"")?;
+                escape_html(self.out, &alt)?;
+                self.out.write_str("\"")?;
+                write_title(self.out, &title)?;
+                self.out.write_str(self.options.void_end())
+            }
+        }
+    }
+
+    fn end_tag(&mut self, tag: Tag) -> fmt::Result {
+        match tag {
+            Tag::Paragraph => self.out.write_str("</p>"),
+            Tag::Heading(level) => write!(self.out, "</h{}>", self.options.heading_level(level)),
+            Tag::BlockQuote => self.out.write_str("</blockquote>"),
+            Tag::CodeBlock(_) => self.out.write_str("</code></pre>"),
+            Tag::List(None) => self.out.write_str("</ul>"),
+            Tag::List(Some(_)) => self.out.write_str("</ol>"),
+            Tag::Item => self.out.write_str("</li>"),
+            Tag::Emphasis => self.out.write_str("</em>"),
+            Tag::Strong => self.out.write_str("</strong>"),
+            Tag::Strikethrough => self.out.write_str("</del>"),
+            Tag::Link { .. } => self.out.write_str("</a>"),
+            // The image is complete once its start tag is written.
+            Tag::Image { .. } => Ok(()),
         }
-        Block {
-            block_type: BlockType::LinkDefinition,
-            raw_text,
-            children,
-            ..
-        } => {
-            let mut result_str = String::new();
-            for v in children {
-                result_str.push_str(&print(v, &mut env))
+    }
+
+    // Consumes the events up to the end of an image, returning their plain
+    // text.
+    fn alt_text(&mut self) -> String {
+        let mut alt = String::new();
+        let mut depth = 0;
+        for (event, _) in self.events.by_ref() {
+            match event {
+                Event::Start(_) => depth += 1,
+                Event::End(_) if depth == 0 => break,
+                Event::End(_) => depth -= 1,
+                Event::Text(s) | Event::Code(s) => alt.push_str(&s),
+                Event::SoftBreak | Event::HardBreak => alt.push('\n'),
+                _ => (),
             }
-            env.insert(raw_text.to_string(), result_str);
-            "".to_string()
         }
-        Block {
-            block_type: BlockType::ReferenceLink,
-            raw_text,
-            ..
-        } => match env.get(&raw_text) {
-            Some(html) => html.to_string(),
-            None => "".to_string(),
+        alt
+    }
+}
+
+// Lets HTML be written into an `io::Write`, keeping the error that
+// `fmt::Write` can not carry.
+struct IoWriter<W> {
+    inner: W,
+    error: Option<io::Error>,
+}
+
+impl<W: io::Write> Write for IoWriter<W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.inner.write_all(s.as_bytes()).map_err(|e| {
+            self.error = Some(e);
+            fmt::Error
+        })
+    }
+}
+
+/// Renders events, e.g. from a `Parser`, as HTML appended to `out`.
+///
+/// `RenderOptions::sourcepos` has no effect, as events carry no positions.
+pub fn push_html<I>(out: &mut String, events: I, options: &RenderOptions)
+where
+    I: Iterator<Item = Event>,
+{
+    // Writing to a String never fails.
+    let _ = render_to(out, events, options);
+}
+
+/// Renders events as HTML into a `fmt::Write`, without building
+/// intermediate strings.
+///
+/// ```
+/// use std::fmt::Write;
+///
+/// let mut html = String::from("<article>");
+/// let parser = markdown::Parser::new("> foo").unwrap();
+/// markdown::render_to(&mut html, parser, &Default::default()).unwrap();
+/// html.write_str("</article>").unwrap();
+/// assert_eq!(html, "<article><blockquote><p>foo</p></blockquote></article>");
+/// ```
+pub fn render_to<W, I>(out: &mut W, events: I, options: &RenderOptions) -> fmt::Result
+where
+    W: Write,
+    I: Iterator<Item = Event>,
+{
+    HtmlWriter {
+        events: events.map(|event| (event, None)),
+        out,
+        options,
+    }
+    .run()
+}
+
+/// Renders events as HTML into an `io::Write` such as a file or a socket.
+///
+/// Output is written in many small pieces, so unbuffered writers are best
+/// wrapped in an `io::BufWriter`.
+pub fn render_to_io<W, I>(out: W, events: I, options: &RenderOptions) -> io::Result<()>
+where
+    W: io::Write,
+    I: Iterator<Item = Event>,
+{
+    let mut writer = IoWriter {
+        inner: out,
+        error: None,
+    };
+    match render_to(&mut writer, events, options) {
+        Ok(()) => Ok(()),
+        Err(fmt::Error) => Err(writer
+            .error
+            .unwrap_or_else(|| io::Error::other("formatter error"))),
+    }
+}
+
+// Like `render_to`, but with the positions needed for sourcepos.
+fn render_parser_to<W: Write>(out: &mut W, parser: Parser, options: &RenderOptions) -> fmt::Result {
+    HtmlWriter {
+        events: parser.into_offset_iter(),
+        out,
+        options,
+    }
+    .run()
+}
+
+pub fn top(input: &str) -> Result<String> {
+    top_with_options(input, &ParseOptions::default(), &RenderOptions::default())
+}
+
+pub fn top_with_options(
+    input: &str,
+    parse_options: &ParseOptions,
+    render_options: &RenderOptions,
+) -> Result<String> {
+    let block_tree = ::parse_with_options(input, parse_options)?;
+    Ok(render(block_tree, render_options))
+}
+
+// Renders a parsed document, with sourcepos if enabled.
+pub fn render(block_tree: Block, options: &RenderOptions) -> String {
+    let mut result_str = String::new();
+    // Writing to a String never fails.
+    let _ = render_parser_to(&mut result_str, Parser::from_block(block_tree), options);
+    result_str
+}
+
+#[test]
+fn test_sourcepos() {
+    let options = RenderOptions {
+        sourcepos: true,
+        ..RenderOptions::default()
+    };
+    let input = "# foo\n\n> bar\n> baz\n\n***";
+    let output = "<h1 data-sourcepos=\"1:1-1:5\">foo</h1>\
+                  <blockquote data-sourcepos=\"3:1-4:5\"><p data-sourcepos=\"3:3-4:5\">bar\nbaz</p></blockquote>\
+                  <hr data-sourcepos=\"6:1-6:3\" />";
+    assert_eq!(
+        top_with_options(input, &ParseOptions::default(), &options).unwrap(),
+        output
+    );
+}
+
+#[test]
+fn test_render_options() {
+    let options = RenderOptions {
+        xhtml: false,
+        hard_breaks: true,
+        heading_offset: 1,
+        ..RenderOptions::default()
+    };
+    let input = "# foo\n\nbar\nbaz\n\n***";
+    let output = "<h2>foo</h2><p>bar<br>baz</p><hr>";
+    assert_eq!(
+        top_with_options(input, &ParseOptions::default(), &options).unwrap(),
+        output
+    );
+
+    let options = RenderOptions {
+        safe: true,
+        ..RenderOptions::default()
+    };
+    let input = "<b>foo</b>";
+    let output = "<p>&lt;b&gt;foo&lt;/b&gt;</p>";
+    assert_eq!(
+        top_with_options(input, &ParseOptions::default(), &options).unwrap(),
+        output
+    );
+
+    let input = "<div>\n*foo*\n</div>";
+    let output = "&lt;div&gt;\n*foo*\n&lt;/div&gt;";
+    assert_eq!(
+        top_with_options(input, &ParseOptions::default(), &options).unwrap(),
+        output
+    );
+
+    let input = "[a](javascript:alert(1)) [b](VBScript:x) <file:///etc/passwd> \
+                 ![c](data:image/png;base64,AA) [d](data:text/html,x) [e](/javascript:x)";
+    let output = "<p><a href=\"\">a</a> <a href=\"\">b</a> \
+                  <a href=\"\">file:///etc/passwd</a> \
+                  <img src=\"data:image/png;base64,AA\" alt=\"c\" /> \
+                  <a href=\"\">d</a> <a href=\"/javascript:x\">e</a></p>";
+    assert_eq!(
+        top_with_options(input, &ParseOptions::default(), &options).unwrap(),
+        output
+    );
+}
+
+#[cfg(test)]
+fn print_inlines(inlines: Vec<Inline>, options: &RenderOptions) -> String {
+    let mut paragraph = Block::new(BlockType::Paragraph, "".to_string(), Position::default());
+    paragraph.inlines = inlines;
+    let mut document = Block::new(BlockType::Document, "".to_string(), Position::default());
+    document.add_block(paragraph);
+    let mut result_str = String::new();
+    push_html(&mut result_str, Parser::from_block(document), options);
+    result_str
+}
+
+#[test]
+fn test_print_inlines() {
+    let options = RenderOptions::default();
+    let inlines = vec![
+        Inline::Text("a < b".to_string()),
+        Inline::HardBreak,
+        Inline::Link {
+            destination: "/url?a=1&b=2".to_string(),
+            title: "\"t\"".to_string(),
+            children: vec![Inline::Strong(vec![Inline::Text("foo".to_string())])],
+        },
+        Inline::SoftBreak,
+        Inline::Image {
+            destination: "/img.png".to_string(),
+            title: "".to_string(),
+            children: vec![Inline::Emphasis(vec![Inline::Text("bar".to_string())])],
         },
+        Inline::Code("<T>".to_string()),
+        Inline::Html("<kbd>".to_string()),
+    ];
+    let output = "<p>a &lt; b<br />\
+                  <a href=\"/url?a=1&amp;b=2\" title=\"&quot;t&quot;\"><strong>foo</strong></a>\n\
+                  <img src=\"/img.png\" alt=\"bar\" />\
+                  <code>&lt;T&gt;</code><kbd></p>";
+    assert_eq!(print_inlines(inlines, &options), output);
+
+    let options = RenderOptions {
+        safe: true,
+        ..RenderOptions::default()
+    };
+    assert_eq!(
+        print_inlines(vec![Inline::Html("<kbd>".to_string())], &options),
+        "<p>&lt;kbd&gt;</p>"
+    );
+}
+
+#[test]
+fn test_hard_line_break() {
+    assert_eq!(top("foo\\\nbar").unwrap(), "<p>foo<br />bar</p>");
+    assert_eq!(top("foo  \nbar").unwrap(), "<p>foo<br />bar</p>");
+}
+
+#[test]
+fn test_parse_options() {
+    let options = ParseOptions {
+        strikethrough: true,
+        tasklist: true,
+    };
+    let input = "~~foo~~\n\n- [x] bar";
+    let output = "<p><del>foo</del></p>\
+                  <ul><li><input checked=\"\" disabled=\"\" type=\"checkbox\" /> bar</li></ul>";
+    assert_eq!(
+        top_with_options(input, &options, &RenderOptions::default()).unwrap(),
+        output
+    );
+}
+
+#[test]
+fn test_render_to_io() {
+    let mut output = vec![];
+    let parser = Parser::new("> *foo*").unwrap();
+    render_to_io(&mut output, parser, &RenderOptions::default()).unwrap();
+    assert_eq!(
+        output,
+        b"<blockquote><p><em>foo</em></p></blockquote>".to_vec()
+    );
+
+    struct Full;
+    impl io::Write for Full {
+        fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+            Err(io::Error::new(io::ErrorKind::WriteZero, "full"))
+        }
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
     }
+    let parser = Parser::new("foo").unwrap();
+    let error = render_to_io(Full, parser, &RenderOptions::default()).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::WriteZero);
+}
+
+#[test]
+fn test_lists() {
+    assert_eq!(
+        top("- a\n- b\n\n- c").unwrap(),
+        "<ul><li><p>a</p></li><li><p>b</p></li><li><p>c</p></li></ul>"
+    );
+    assert_eq!(
+        top("3. a\n4. b").unwrap(),
+        "<ol start=\"3\"><li>a</li><li>b</li></ol>"
+    );
+}
+
+#[test]
+fn test_reference_links() {
+    let definitions = "\n\n[foo bar]: /url \"title\"";
+    assert_eq!(
+        top(&format!("see [the *docs*][Foo  Bar] here{}", definitions)).unwrap(),
+        "<p>see <a href=\"/url\" title=\"title\">the <em>docs</em></a> here</p>"
+    );
+    assert_eq!(
+        top(&format!("- [foo bar][] and [FOO BAR]{}", definitions)).unwrap(),
+        "<ul><li><a href=\"/url\" title=\"title\">foo bar</a> and \
+         <a href=\"/url\" title=\"title\">FOO BAR</a></li></ul>"
+    );
+    assert_eq!(
+        top("[foo][bar]\n\n[foo]: /url").unwrap(),
+        "<p>[foo][bar]</p>"
+    );
+    assert_eq!(
+        top("[foo][bar][baz]\n\n[baz]: /url1\n[bar]: /url2").unwrap(),
+        "<p><a href=\"/url2\">foo</a><a href=\"/url1\">baz</a></p>"
+    );
+    // Links do not contain other links.
+    assert_eq!(
+        top("[foo [bar]][foo]\n\n[foo]: /url\n[bar]: /bar").unwrap(),
+        "<p>[foo <a href=\"/bar\">bar</a>]<a href=\"/url\">foo</a></p>"
+    );
+}
+
+#[test]
+fn test_inline_links() {
+    assert_eq!(
+        top("[link](/uri \"title\") and [link](<>)").unwrap(),
+        "<p><a href=\"/uri\" title=\"title\">link</a> and <a href=\"\">link</a></p>"
+    );
+    assert_eq!(
+        top("[link](   foo(and(bar))\n  'a &amp; \\'b\\''  )").unwrap(),
+        "<p><a href=\"foo(and(bar))\" title=\"a &amp; 'b'\">link</a></p>"
+    );
+    assert_eq!(
+        top("[link](foo\\\\b&auml;'&)").unwrap(),
+        "<p><a href=\"foo%5Cb%C3%A4&#x27;&amp;\">link</a></p>"
+    );
+    assert_eq!(
+        top("[foo](not a link)\n\n[foo]: /url").unwrap(),
+        "<p><a href=\"/url\">foo</a>(not a link)</p>"
+    );
+    assert_eq!(
+        top("[foo [bar](/uri)](/uri)").unwrap(),
+        "<p>[foo <a href=\"/uri\">bar</a>](/uri)</p>"
+    );
+}
+
+#[test]
+fn test_images() {
+    assert_eq!(
+        top("My ![foo *bar*](/train.jpg  \"title\"   )").unwrap(),
+        "<p>My <img src=\"/train.jpg\" alt=\"foo bar\" title=\"title\" /></p>"
+    );
+    assert_eq!(
+        top("[![moon](moon.jpg)](/uri)").unwrap(),
+        "<p><a href=\"/uri\"><img src=\"moon.jpg\" alt=\"moon\" /></a></p>"
+    );
+    assert_eq!(
+        top("![[[foo](uri1)](uri2)](uri3)").unwrap(),
+        "<p><img src=\"uri3\" alt=\"[foo](uri2)\" /></p>"
+    );
+    assert_eq!(
+        top("![Foo] and \\![foo]\n\n[foo]: /url").unwrap(),
+        "<p><img src=\"/url\" alt=\"Foo\" /> and !<a href=\"/url\">foo</a></p>"
+    );
+}
+
+#[test]
+fn test_autolinks() {
+    assert_eq!(
+        top("<http://foo.bar.baz/test?q=hello&id=22&boolean>").unwrap(),
+        "<p><a href=\"http://foo.bar.baz/test?q=hello&amp;id=22&amp;boolean\">\
+         http://foo.bar.baz/test?q=hello&amp;id=22&amp;boolean</a></p>"
+    );
+    assert_eq!(
+        top("<http://example.com/\\[\\> <a+b+c:d>").unwrap(),
+        "<p><a href=\"http://example.com/%5C%5B%5C\">http://example.com/\\[\\</a> \
+         <a href=\"a+b+c:d\">a+b+c:d</a></p>"
+    );
+    assert_eq!(
+        top("<foo+special@Bar.baz-bar0.com>").unwrap(),
+        "<p><a href=\"mailto:foo+special@Bar.baz-bar0.com\">foo+special@Bar.baz-bar0.com</a></p>"
+    );
+    assert_eq!(
+        top("<http://foo.bar/baz bim> <m:abc> <foo\\+@bar.example.com>").unwrap(),
+        "<p>&lt;http://foo.bar/baz bim&gt; &lt;m:abc&gt; &lt;foo+@bar.example.com&gt;</p>"
+    );
 }
 
-pub fn top(input_str: &str) -> String {
-    // Add line feed.
-    let mut input = String::new();
-    input.push_str(input_str);
-    input.push_str("\n");
+#[test]
+fn test_inline_html() {
+    assert_eq!(
+        top("Press <kbd>Ctrl</kbd> <span class=\"x\"\ntitle='y'>here</span>").unwrap(),
+        "<p>Press <kbd>Ctrl</kbd> <span class=\"x\"\ntitle='y'>here</span></p>"
+    );
+    assert_eq!(
+        top("foo <!-- note --> <?php echo $a; ?> <!ELEMENT br EMPTY> <![CDATA[>&<]]>").unwrap(),
+        "<p>foo <!-- note --> <?php echo $a; ?> <!ELEMENT br EMPTY> <![CDATA[>&<]]></p>"
+    );
+    assert_eq!(
+        top("<33> <a h*#ref=\"hi\"> </a href=\"foo\"> <!-- a -- b -->").unwrap(),
+        "<p>&lt;33&gt; &lt;a h*#ref=&quot;hi&quot;&gt; &lt;/a href=&quot;foo&quot;&gt; \
+         &lt;!-- a -- b --&gt;</p>"
+    );
 
-    //let mut input = convert_tabs(&input);
-    let mut block_tree = block_parser::top(&input);
-    inline_parser::top(&mut block_tree);
-    let mut env = HashMap::new();
-    print(block_tree, &mut env)
+    let options = RenderOptions {
+        safe: true,
+        ..RenderOptions::default()
+    };
+    assert_eq!(
+        top_with_options("a <kbd>b</kbd>", &ParseOptions::default(), &options).unwrap(),
+        "<p>a &lt;kbd&gt;b&lt;/kbd&gt;</p>"
+    );
 }
@@ -0,0 +1,66 @@
+/// The inline content of a leaf block such as a paragraph or a heading.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Inline {
+    /// Literal text, with escapes and entities already resolved.
+    Text(String),
+    Emphasis(Vec<Inline>),
+    Strong(Vec<Inline>),
+    Strikethrough(Vec<Inline>),
+    /// The content of a code span.
+    Code(String),
+    Link {
+        destination: String,
+        title: String,
+        children: Vec<Inline>,
+    },
+    Image {
+        destination: String,
+        title: String,
+        children: Vec<Inline>,
+    },
+    HardBreak,
+    SoftBreak,
+    /// Raw HTML, passed through verbatim.
+    Html(String),
+}
+
+impl Inline {
+    /// The plain text of this inline and its children, e.g. for `alt`.
+    pub fn text(&self) -> String {
+        let mut result = String::new();
+        self.push_text(&mut result);
+        result
+    }
+
+    fn push_text(&self, result: &mut String) {
+        match *self {
+            Inline::Text(ref s) | Inline::Code(ref s) => result.push_str(s),
+            Inline::Emphasis(ref children)
+            | Inline::Strong(ref children)
+            | Inline::Strikethrough(ref children)
+            | Inline::Link { ref children, .. }
+            | Inline::Image { ref children, .. } => {
+                for child in children {
+                    child.push_text(result);
+                }
+            }
+            Inline::HardBreak | Inline::SoftBreak => result.push('\n'),
+            Inline::Html(_) => (),
+        }
+    }
+}
+
+#[test]
+fn test_text() {
+    let inline = Inline::Link {
+        destination: "/url".to_string(),
+        title: "".to_string(),
+        children: vec![
+            Inline::Text("foo ".to_string()),
+            Inline::Emphasis(vec![Inline::Text("bar".to_string())]),
+            Inline::Html("<b>".to_string()),
+            Inline::Code("baz".to_string()),
+        ],
+    };
+    assert_eq!(inline.text(), "foo barbaz");
+}
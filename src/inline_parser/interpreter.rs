@@ -1,93 +1,630 @@
 use super::lexer::Rule;
-use htmlescape::decode_html;
+use super::punctuation::is_punctuation;
+use super::Definitions;
+use block_parser::{
+    link_destination, link_label, link_title, normalize_label, skip_spaces, unescape,
+};
+use entity::entity;
+use error::{Error, Result};
+use inline::Inline;
+use options::ParseOptions;
 use pest::iterators::Pairs;
-
-const ESCAPED_CHARACTERS: [(char, &'static str); 32] = [
-    ('!', "!"),
-    ('"', "&quot;"),
-    ('#', "#"),
-    ('$', "$"),
-    ('%', "%"),
-    ('&', "&amp;"),
-    ('\'', "\'"),
-    ('(', "("),
-    (')', ")"),
-    ('*', "*"),
-    ('+', "+"),
-    (',', ","),
-    ('-', "-"),
-    ('.', "."),
-    ('/', "/"),
-    (':', ":"),
-    (';', ";"),
-    ('<', "&lt;"),
-    ('=', "="),
-    ('>', "&gt;"),
-    ('?', "?"),
-    ('@', "@"),
-    ('[', "["),
-    ('\\', "\\"),
-    (']', "]"),
-    ('^', "^"),
-    ('_', "_"),
-    ('`', "`"),
-    ('{', "{"),
-    ('|', "|"),
-    ('}', "}"),
-    ('~', "~"),
-];
-
-fn emphasize(s: &str) -> String {
-    format!("<em>{}</em>", s)
-}
-
-fn hard_line_break(_s: &str) -> String {
-    String::from("<br />")
-}
+use std::mem;
 
 fn escape_backslash(s: &str) -> String {
     let mut ch = s.chars();
 
-    // TODO error handing
-    //if &ch.count() != 2 {
-    //    panic!("s length should be 2");
-    //    return "".to_string()
-    //}
+    let c = match (ch.next(), ch.next()) {
+        (Some('\\'), Some(c)) => c,
+        _ => return s.to_string(),
+    };
+
+    if c.is_ascii_punctuation() {
+        c.to_string()
+    } else {
+        format!("\\{}", c)
+    }
+}
+
+// An unknown entity is literal text.
+fn escape_html_entity(s: &str) -> String {
+    match entity(s) {
+        Some((decoded, _)) => decoded,
+        None => s.to_string(),
+    }
+}
+
+// The content of a code span without its backtick strings. Line endings
+// become spaces, and a space is stripped from both ends unless it is all
+// spaces.
+fn code_span(s: &str) -> Inline {
+    let len = s.len() - s.trim_start_matches('`').len();
+    let content = s[len..s.len() - len].replace('\n', " ");
+    let content = if content.starts_with(' ')
+        && content.ends_with(' ')
+        && !content.trim_matches(' ').is_empty()
+    {
+        content[1..content.len() - 1].to_string()
+    } else {
+        content
+    };
+    Inline::Code(content)
+}
+
+// A link to the URI or email address in an autolink, which is also its
+// text. Backslashes in it are not escapes.
+fn autolink(s: &str, email: bool) -> Inline {
+    let address = &s[1..s.len() - 1];
+    let destination = if email {
+        format!("mailto:{}", address)
+    } else {
+        address.to_string()
+    };
+    Inline::Link {
+        destination,
+        title: String::new(),
+        children: vec![Inline::Text(address.to_string())],
+    }
+}
+
+// Appends text, merging it into the previous node when that is text too.
+fn push_text(result: &mut Vec<Inline>, s: &str) {
+    if let Some(&mut Inline::Text(ref mut text)) = result.last_mut() {
+        text.push_str(s);
+        return;
+    }
+    result.push(Inline::Text(s.to_string()));
+}
+
+// Spaces at the end of a line are not part of the text.
+fn push_soft_break(result: &mut Vec<Inline>) {
+    if let Some(&mut Inline::Text(ref mut text)) = result.last_mut() {
+        let len = text.trim_end_matches(' ').len();
+        text.truncate(len);
+    }
+    result.push(Inline::SoftBreak);
+}
+
+// Emphasis, links and images nested deeper than this are text, as the
+// passes over the inlines recurse into every one of them.
+const MAX_NESTING: usize = 100;
+
+// An inline in the making. Delimiter runs keep their place while emphasis
+// is matched up, so that matching does not move the other nodes around.
+enum Node {
+    // An inline, with how deeply inlines nest in it.
+    Inline(Inline, usize),
+    // What is left of a run of `c`, with the number of emphasis inlines
+    // that end right before it, and those that start right after it in the
+    // order they were matched, by the number of delimiters they take up.
+    Delimiter {
+        c: char,
+        text: String,
+        closes: usize,
+        opens: Vec<usize>,
+    },
+}
+
+// Emphasis that has started, with the inlines before it and how deeply
+// they nest. Emphasis nested too deeply keeps its delimiters as text
+// instead.
+struct Parent {
+    n: usize,
+    inlines: Option<(Vec<Inline>, usize)>,
+}
 
-    if ch.next().unwrap() != '\\' {
-        panic!("s should start with \\");
+// What `n` delimiters of `c` at both ends make of the inlines between
+// them.
+fn emphasis(c: char, n: usize, children: Vec<Inline>) -> Inline {
+    if c == '~' {
+        Inline::Strikethrough(children)
+    } else if n == 2 {
+        Inline::Strong(children)
+    } else {
+        Inline::Emphasis(children)
     }
+}
 
-    let c = ch.next().unwrap();
+fn delimiters(c: char, n: usize) -> String {
+    c.to_string().repeat(n)
+}
 
-    match ESCAPED_CHARACTERS.binary_search_by_key(&c, |&(a, _)| a) {
-        Ok(s) => {
-            let (_, b) = ESCAPED_CHARACTERS[s];
-            b.to_string()
+// Turns the nodes into inlines, wrapping what is between the two ends of
+// each emphasis in it, and merges adjacent text. Also returns how deeply
+// the inlines nest.
+fn build(nodes: Vec<Node>) -> (Vec<Inline>, usize) {
+    let mut parents: Vec<Parent> = vec![];
+    let mut result = vec![];
+    let mut depth = 0;
+    for node in nodes {
+        match node {
+            Node::Inline(Inline::Text(s), _) => push_text(&mut result, &s),
+            Node::Inline(Inline::SoftBreak, _) => push_soft_break(&mut result),
+            Node::Inline(inline, inline_depth) => {
+                result.push(inline);
+                depth = depth.max(inline_depth);
+            }
+            Node::Delimiter {
+                c,
+                text,
+                closes,
+                opens,
+            } => {
+                for _ in 0..closes {
+                    let parent = match parents.pop() {
+                        Some(parent) => parent,
+                        None => break,
+                    };
+                    let (inlines, parent_depth) = match parent.inlines {
+                        Some(inlines) => inlines,
+                        None => {
+                            push_text(&mut result, &delimiters(c, parent.n));
+                            continue;
+                        }
+                    };
+                    let children = mem::replace(&mut result, inlines);
+                    let emphasis_depth = depth + 1;
+                    depth = parent_depth.max(emphasis_depth);
+                    if emphasis_depth > MAX_NESTING {
+                        // Links in it nest too deeply.
+                        depth = parent_depth.max(emphasis_depth - 1);
+                        push_text(&mut result, &delimiters(c, parent.n));
+                        for child in children {
+                            match child {
+                                Inline::Text(s) => push_text(&mut result, &s),
+                                child => result.push(child),
+                            }
+                        }
+                        push_text(&mut result, &delimiters(c, parent.n));
+                    } else {
+                        result.push(emphasis(c, parent.n, children));
+                    }
+                }
+                if !text.is_empty() {
+                    push_text(&mut result, &text);
+                }
+                // Emphasis matched later is further out.
+                for n in opens.into_iter().rev() {
+                    let inlines = if parents.len() < MAX_NESTING {
+                        let inlines = mem::take(&mut result);
+                        let parent_depth = mem::replace(&mut depth, 0);
+                        Some((inlines, parent_depth))
+                    } else {
+                        push_text(&mut result, &delimiters(c, n));
+                        None
+                    };
+                    parents.push(Parent { n, inlines });
+                }
+            }
         }
-        Err(_) => format!("\\{}", c),
     }
+    (result, depth)
 }
 
-fn escape_html_entity(s: &str) -> String {
-    decode_html(s).unwrap().to_string()
+// Whether a run of `c` between `before` and `after` can open and close
+// emphasis. The start and end of the text count as whitespace.
+fn flanking(c: char, before: Option<char>, after: Option<char>) -> (bool, bool) {
+    let before = before.unwrap_or(' ');
+    let after = after.unwrap_or(' ');
+    let left = !after.is_whitespace()
+        && (!is_punctuation(after) || before.is_whitespace() || is_punctuation(before));
+    let right = !before.is_whitespace()
+        && (!is_punctuation(before) || after.is_whitespace() || is_punctuation(after));
+    if c == '_' {
+        // `_` does not emphasize within words.
+        (
+            left && (!right || is_punctuation(before)),
+            right && (!left || is_punctuation(after)),
+        )
+    } else {
+        (left, right)
+    }
 }
 
-pub fn top(tokens: Pairs<Rule>) -> String {
-    let mut result = String::new();
+// A run of `*`, `_` or `~` on the delimiter stack, with the index of its
+// node.
+struct Delimiter {
+    index: usize,
+    c: char,
+    count: usize,
+    // The length of the whole run, for the rule of 3.
+    length: usize,
+    can_open: bool,
+    can_close: bool,
+    // The delimiter before it that is still in use, so that the search for
+    // an opener skips those that are used up or matched over.
+    prev: Option<usize>,
+}
+
+// An opening bracket in the text and in the nodes, which holds it as text
+// of its own until it is closed.
+struct Bracket {
+    start: usize,
+    index: usize,
+    image: bool,
+    // Brackets before a link can not start another link, as links do not
+    // nest.
+    active: bool,
+    // How deeply the inlines after it nest so far.
+    depth: usize,
+}
+
+struct Interpreter<'a> {
+    text: &'a str,
+    options: &'a ParseOptions,
+    definitions: &'a Definitions,
+    result: Vec<Node>,
+    brackets: Vec<Bracket>,
+    delimiters: Vec<Delimiter>,
+}
+
+impl<'a> Interpreter<'a> {
+    fn push(&mut self, inline: Inline) {
+        self.result.push(Node::Inline(inline, 0));
+    }
+
+    fn push_text(&mut self, s: &str) {
+        let last = self.result.len().wrapping_sub(1);
+        let is_bracket = self.brackets.last().is_some_and(|b| b.index == last);
+        if !is_bracket {
+            if let Some(&mut Node::Inline(Inline::Text(ref mut text), _)) = self.result.last_mut() {
+                text.push_str(s);
+                return;
+            }
+        }
+        self.push(Inline::Text(s.to_string()));
+    }
+
+    fn open_bracket(&mut self, start: usize, image: bool) {
+        let text = if image { "![" } else { "[" };
+        self.push(Inline::Text(text.to_string()));
+        self.brackets.push(Bracket {
+            start,
+            index: self.result.len() - 1,
+            image,
+            active: true,
+            depth: 0,
+        });
+    }
+
+    // Adds an inline that has inlines nested in it.
+    fn push_nested(&mut self, inline: Inline, depth: usize) {
+        self.result.push(Node::Inline(inline, depth));
+        self.nest(depth);
+    }
+
+    fn nest(&mut self, depth: usize) {
+        if let Some(bracket) = self.brackets.last_mut() {
+            bracket.depth = bracket.depth.max(depth);
+        }
+    }
+
+    fn push_delimiter_run(&mut self, start: usize, end: usize) {
+        let run = &self.text[start..end];
+        let c = run.chars().next().unwrap_or('*');
+        // Strikethrough takes one or two tildes.
+        if c == '~' && (!self.options.strikethrough || run.len() > 2) {
+            return self.push_text(run);
+        }
+        let before = self.text[..start].chars().next_back();
+        let after = self.text[end..].chars().next();
+        let (can_open, can_close) = flanking(c, before, after);
+        if !can_open && !can_close {
+            return self.push_text(run);
+        }
+        self.result.push(Node::Delimiter {
+            c,
+            text: run.to_string(),
+            closes: 0,
+            opens: vec![],
+        });
+        let prev = self.delimiters.len().checked_sub(1);
+        self.delimiters.push(Delimiter {
+            index: self.result.len() - 1,
+            c,
+            count: run.len(),
+            length: run.len(),
+            can_open,
+            can_close,
+            prev,
+        });
+    }
+
+    // Takes `n` delimiters off the run at `i` for emphasis that starts or
+    // ends there.
+    fn use_delimiters(&mut self, i: usize, n: usize, is_opener: bool) {
+        self.delimiters[i].count -= n;
+        if let Node::Delimiter {
+            ref mut text,
+            ref mut closes,
+            ref mut opens,
+            ..
+        } = self.result[self.delimiters[i].index]
+        {
+            let len = text.len() - n;
+            text.truncate(len);
+            if is_opener {
+                opens.push(n);
+            } else {
+                *closes += 1;
+            }
+        }
+    }
+
+    // Takes the closer at `i` out of the search for openers.
+    fn remove_closer(&mut self, i: usize) {
+        let prev = self.delimiters[i].prev;
+        if let Some(next) = self.delimiters.get_mut(i + 1) {
+            next.prev = prev;
+        }
+    }
+
+    // Adds emphasis, or strong emphasis if both the opener and the closer
+    // have two delimiters to spare, around the nodes between them.
+    // Strikethrough takes up all of its delimiters.
+    fn add_emphasis(&mut self, opener: usize, closer: usize) {
+        let count = self.delimiters[opener].count;
+        let n = if self.delimiters[opener].c == '~' {
+            count
+        } else if count >= 2 && self.delimiters[closer].count >= 2 {
+            2
+        } else {
+            1
+        };
+        self.use_delimiters(opener, n, true);
+        self.use_delimiters(closer, n, false);
+        // The delimiters in between can no longer be matched.
+        self.delimiters[closer].prev = if self.delimiters[opener].count > 0 {
+            Some(opener)
+        } else {
+            self.delimiters[opener].prev
+        };
+        if self.delimiters[closer].count == 0 {
+            self.remove_closer(closer);
+        }
+    }
+
+    // Matches up the delimiter runs from `bottom` on the stack into
+    // emphasis, and takes them off the stack.
+    fn process_emphasis(&mut self, bottom: usize) {
+        // Where the search for an opener stops, by the character, whether
+        // the closer can open and its length modulo 3, as the search can
+        // only fail again for the same kind of closer.
+        let mut openers_bottom = [[[bottom; 3]; 2]; 3];
+        let mut closer = bottom;
+        while closer < self.delimiters.len() {
+            let (c, can_open, length) = {
+                let delimiter = &self.delimiters[closer];
+                if !delimiter.can_close {
+                    closer += 1;
+                    continue;
+                }
+                (delimiter.c, delimiter.can_open, delimiter.length)
+            };
+            let kind = match c {
+                '*' => 0,
+                '_' => 1,
+                _ => 2,
+            };
+            let opener_bottom = &mut openers_bottom[kind][can_open as usize][length % 3];
+            let mut opener = self.delimiters[closer].prev;
+            while let Some(i) = opener {
+                if i < *opener_bottom {
+                    opener = None;
+                    break;
+                }
+                let delimiter = &self.delimiters[i];
+                // The rule of 3: a run that can both open and close only
+                // matches a run whose length does not add up to a multiple
+                // of 3 with its own, unless both are multiples of 3. Tildes
+                // only match a run of the same length.
+                let sum = delimiter.length + length;
+                let is_odd_match = if c == '~' {
+                    delimiter.length != length
+                } else {
+                    (delimiter.can_close || can_open)
+                        && sum.is_multiple_of(3)
+                        && (!delimiter.length.is_multiple_of(3) || !length.is_multiple_of(3))
+                };
+                if delimiter.c == c && delimiter.can_open && !is_odd_match {
+                    break;
+                }
+                opener = delimiter.prev;
+            }
+            match opener {
+                Some(opener) => {
+                    self.add_emphasis(opener, closer);
+                    if self.delimiters[closer].count == 0 {
+                        closer += 1;
+                    }
+                }
+                None => {
+                    *opener_bottom = closer;
+                    if !can_open {
+                        self.remove_closer(closer);
+                    }
+                    closer += 1;
+                }
+            }
+        }
+        self.delimiters.truncate(bottom);
+    }
+
+    // The destination and title of an inline link whose text ends at `end`,
+    // e.g. `(/url "title")`, and where it ends.
+    fn inline_link(&self, end: usize) -> Option<(String, String, usize)> {
+        if !self.text[end..].starts_with('(') {
+            return None;
+        }
+        let destination_start = skip_spaces(self.text, end + 1);
+        let (destination_end, destination) = if self.text[destination_start..].starts_with(')') {
+            (destination_start, "")
+        } else {
+            link_destination(self.text, destination_start)?
+        };
+        let title_start = skip_spaces(self.text, destination_end);
+        let (title_end, title) = match link_title(self.text, title_start) {
+            Some(title) if title_start > destination_end => title,
+            _ => (destination_end, ""),
+        };
+        let link_end = skip_spaces(self.text, title_end);
+        if !self.text[link_end..].starts_with(')') {
+            return None;
+        }
+        Some((unescape(destination), unescape(title), link_end + 1))
+    }
+
+    // The definition of the reference link whose text ends at `end`, and
+    // where its label ends. The label is either the one after the text, or
+    // the text itself when followed by `[]` or nothing.
+    fn reference(&self, bracket: &Bracket, end: usize) -> Option<(String, String, usize)> {
+        let (label, label_end) = match link_label(self.text, end) {
+            Some(label_end) => (&self.text[end + 1..label_end - 1], label_end),
+            None => {
+                if link_label(self.text, bracket.start) != Some(end) {
+                    return None;
+                }
+                let label_end = if self.text[end..].starts_with("[]") {
+                    end + 2
+                } else {
+                    end
+                };
+                (&self.text[bracket.start + 1..end - 1], label_end)
+            }
+        };
+        self.definitions
+            .get(&normalize_label(label))
+            .map(|(destination, title)| (destination.clone(), title.clone(), label_end))
+    }
+
+    // Closes the last bracket with the one ending at `end`, and returns where
+    // the link or image, if there is one, ends.
+    fn close_bracket(&mut self, end: usize) -> usize {
+        let bracket = match self.brackets.pop() {
+            Some(bracket) => bracket,
+            None => {
+                self.push_text("]");
+                return end;
+            }
+        };
+        let link = if bracket.active && bracket.depth < MAX_NESTING {
+            self.inline_link(end)
+                .or_else(|| self.reference(&bracket, end))
+        } else {
+            None
+        };
+        let (destination, title, link_end) = match link {
+            Some(link) => link,
+            None => {
+                self.nest(bracket.depth);
+                self.push_text("]");
+                return end;
+            }
+        };
+
+        let bottom = match self
+            .delimiters
+            .binary_search_by_key(&bracket.index, |delimiter| delimiter.index)
+        {
+            Ok(i) | Err(i) => i,
+        };
+        self.process_emphasis(bottom);
+        let (children, depth) = build(self.result.split_off(bracket.index + 1));
+        if depth >= MAX_NESTING {
+            // Emphasis in it nests too deeply, so the link is text after the
+            // bracket that is left in place.
+            for child in children {
+                self.push_nested(child, depth);
+            }
+            self.push_text(&self.text[end - 1..link_end]);
+            return link_end;
+        }
+        self.result.pop();
+        let link = if bracket.image {
+            Inline::Image {
+                destination,
+                title,
+                children,
+            }
+        } else {
+            Inline::Link {
+                destination,
+                title,
+                children,
+            }
+        };
+        self.push_nested(link, depth + 1);
+        if bracket.image {
+            return link_end;
+        }
+        // Brackets below one that is already inactive are inactive too.
+        for bracket in self.brackets.iter_mut().rev().filter(|bracket| !bracket.image) {
+            if !bracket.active {
+                break;
+            }
+            bracket.active = false;
+        }
+        link_end
+    }
+
+    fn run(mut self, tokens: Pairs<Rule>) -> Result<Vec<Inline>> {
+        // Tokens already taken up by the end of a link.
+        let mut skip_to = 0;
 
-    for token in tokens {
-        match token.as_rule() {
-            Rule::escaped_slash => result.push_str(&escape_backslash(token.as_str())),
-            Rule::html_entity => result.push_str(&escape_html_entity(token.as_str())),
-            Rule::emphasis => result.push_str(&emphasize(token.as_str())),
-            Rule::hard_line_break => result.push_str(&hard_line_break(token.as_str())),
-            Rule::other => result.push_str(token.as_str()),
-            Rule::EOI => (),
-            _ => panic!("Error: No token is parsed."),
+        for token in tokens {
+            let span = token.as_span();
+            if span.start() < skip_to {
+                // What is left of a token that a link ends in is text.
+                if span.end() > skip_to {
+                    self.push_text(&self.text[skip_to..span.end()]);
+                }
+                continue;
+            }
+            match token.as_rule() {
+                Rule::escaped_slash => self.push_text(&escape_backslash(token.as_str())),
+                Rule::code_span => self.push(code_span(token.as_str())),
+                Rule::backticks => self.push_text(token.as_str()),
+                Rule::uri_autolink => self.push(autolink(token.as_str(), false)),
+                Rule::email_autolink => self.push(autolink(token.as_str(), true)),
+                Rule::html_inline => self.push(Inline::Html(token.as_str().to_string())),
+                Rule::html_entity => self.push_text(&escape_html_entity(token.as_str())),
+                Rule::delimiter_run => self.push_delimiter_run(span.start(), span.end()),
+                Rule::hard_line_break => self.push(Inline::HardBreak),
+                Rule::image_open => self.open_bracket(span.start() + 1, true),
+                Rule::link_open => self.open_bracket(span.start(), false),
+                Rule::link_close => skip_to = self.close_bracket(span.end()),
+                Rule::other => match token.as_str() {
+                    "\n" => self.push(Inline::SoftBreak),
+                    s => self.push_text(s),
+                },
+                Rule::EOI => (),
+                _ => {
+                    let (line, column) = span.start_pos().line_col();
+                    return Err(Error::Inline { line, column });
+                }
+            }
         }
+        self.process_emphasis(0);
+        let (inlines, _) = build(self.result);
+        Ok(inlines)
     }
-    result
+}
+
+pub fn top(
+    text: &str,
+    tokens: Pairs<Rule>,
+    options: &ParseOptions,
+    definitions: &Definitions,
+) -> Result<Vec<Inline>> {
+    let interpreter = Interpreter {
+        text,
+        options,
+        definitions,
+        result: vec![],
+        brackets: vec![],
+        delimiters: vec![],
+    };
+    interpreter.run(tokens)
 }
 
 #[test]
@@ -95,6 +632,10 @@ fn test_escape_backslash() {
     let input = "\\!";
     let output = "!";
     assert_eq!(escape_backslash(input), output);
+
+    let input = "\\a";
+    let output = "\\a";
+    assert_eq!(escape_backslash(input), output);
 }
 
 #[test]
@@ -105,18 +646,116 @@ fn test_escape_html_entity() {
 }
 
 #[test]
-fn test_emphasize() {
-    let input = "aaa";
-    let output = "<em>aaa</em>";
-    assert_eq!(emphasize(input), output);
+fn test_escape_unknown_html_entity() {
+    let input = "&MadeUpEntity;";
+    let output = "&MadeUpEntity;";
+    assert_eq!(escape_html_entity(input), output);
 }
+
+#[test]
+fn test_strikethrough() {
+    let mut options = ParseOptions::default();
+    let input = "~~a &amp; \\* [l](/u) `c`~~ ~b~ ~~~c~~~ ~~d~";
+    let document = ::parse_with_options(input, &options).unwrap();
+    assert_eq!(
+        document.children[0].inlines[0],
+        Inline::Text("~~a & * ".to_string())
+    );
+
+    options.strikethrough = true;
+    let document = ::parse_with_options(input, &options).unwrap();
+    assert_eq!(
+        document.children[0].inlines,
+        vec![
+            Inline::Strikethrough(vec![
+                Inline::Text("a & * ".to_string()),
+                Inline::Link {
+                    destination: "/u".to_string(),
+                    title: "".to_string(),
+                    children: vec![Inline::Text("l".to_string())],
+                },
+                Inline::Text(" ".to_string()),
+                Inline::Code("c".to_string()),
+            ]),
+            Inline::Text(" ".to_string()),
+            Inline::Strikethrough(vec![Inline::Text("b".to_string())]),
+            Inline::Text(" ~~~c~~~ ~~d~".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_soft_break() {
+    let mut result = vec![];
+    push_text(&mut result, "foo");
+    push_text(&mut result, "  ");
+    push_soft_break(&mut result);
+    push_text(&mut result, "bar");
+    assert_eq!(
+        result,
+        vec![
+            Inline::Text("foo".to_string()),
+            Inline::SoftBreak,
+            Inline::Text("bar".to_string()),
+        ]
+    );
+}
+
 #[test]
-fn test_hard_line_break() {
-    let input = "\\\n";
-    let output = "<br />";
-    assert_eq!(hard_line_break(input), output);
-
-    let input = "  \n";
-    let output = "<br />";
-    assert_eq!(hard_line_break(input), output);
+fn test_code_span() {
+    assert_eq!(code_span("`foo`"), Inline::Code("foo".to_string()));
+    assert_eq!(code_span("`` `foo` ``"), Inline::Code("`foo`".to_string()));
+    assert_eq!(code_span("` a\nb  `"), Inline::Code("a b ".to_string()));
+    assert_eq!(code_span("`  `"), Inline::Code("  ".to_string()));
+}
+
+#[test]
+fn test_flanking() {
+    assert_eq!(flanking('*', None, Some('a')), (true, false));
+    assert_eq!(flanking('*', Some('a'), None), (false, true));
+    assert_eq!(flanking('*', Some('a'), Some('b')), (true, true));
+    assert_eq!(flanking('*', Some(' '), Some(' ')), (false, false));
+    assert_eq!(flanking('*', Some('a'), Some('"')), (false, true));
+    assert_eq!(flanking('_', Some('a'), Some('b')), (false, false));
+    assert_eq!(flanking('_', Some('-'), Some('(')), (true, true));
+    assert_eq!(flanking('_', Some(' '), Some('(')), (true, false));
+    assert_eq!(flanking('_', Some(')'), None), (false, true));
+    // Symbols and punctuation outside of ASCII count as punctuation.
+    assert_eq!(flanking('*', Some('€'), Some('a')), (true, false));
+    assert_eq!(flanking('*', Some('a'), Some('€')), (false, true));
+    assert_eq!(flanking('_', Some('a'), Some('“')), (false, true));
+}
+
+#[test]
+fn test_autolink() {
+    assert_eq!(
+        autolink("<foo@bar.example.com>", true),
+        Inline::Link {
+            destination: "mailto:foo@bar.example.com".to_string(),
+            title: "".to_string(),
+            children: vec![Inline::Text("foo@bar.example.com".to_string())],
+        }
+    );
+}
+
+#[test]
+fn test_nesting_limit() {
+    fn depth(inlines: &[Inline]) -> usize {
+        inlines
+            .iter()
+            .map(|inline| match *inline {
+                Inline::Emphasis(ref children)
+                | Inline::Strong(ref children)
+                | Inline::Image { ref children, .. } => 1 + depth(children),
+                _ => 0,
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    let n = 2 * MAX_NESTING + 10;
+    let document = ::parse(&("*".repeat(n) + "a" + &"*".repeat(n))).unwrap();
+    assert_eq!(depth(&document.children[0].inlines), MAX_NESTING);
+    let document = ::parse(&("![".repeat(n) + "a" + &"](u)".repeat(n))).unwrap();
+    assert_eq!(depth(&document.children[0].inlines), MAX_NESTING);
 }
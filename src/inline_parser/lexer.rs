@@ -1,12 +1,14 @@
 use pest::error::Error;
 use pest::iterators::Pairs;
+#[cfg(test)]
+use pest::{consumes_to, parses_to};
 use pest::Parser;
 
 #[derive(Parser)]
 #[grammar = "inline_parser/inline.pest"]
 struct InlineParser;
 
-pub fn lex(line: &str) -> Result<Pairs<Rule>, Error<Rule>> {
+pub fn lex(line: &str) -> Result<Pairs<'_, Rule>, Error<Rule>> {
     InlineParser::parse(Rule::inline, line)
 }
 
@@ -81,20 +83,113 @@ fn test_parse_sequence() {
 }
 
 #[test]
-fn test_emphasis_rule1() {
+fn test_link_brackets() {
     parses_to! {
         parser: InlineParser,
-        input: "foo*bar*",
+        input: "[a]\\]",
         rule: Rule::inline,
         tokens: [
-          other(0, 1, [
-          ]),
-          other(1, 2, [
-          ]),
-          other(2, 3, [
-          ]),
-          emphasis(4, 7, [
-          ]),
+            link_open(0, 1, []),
+            other(1, 2, []),
+            link_close(2, 3, []),
+            escaped_slash(3, 5, []),
+        ]
+    };
+}
+
+#[test]
+fn test_code_span() {
+    parses_to! {
+        parser: InlineParser,
+        input: "``a`b```c``",
+        rule: Rule::inline,
+        tokens: [
+            code_span(0, 11, []),
+        ]
+    };
+
+    parses_to! {
+        parser: InlineParser,
+        input: "``a`",
+        rule: Rule::inline,
+        tokens: [
+            backticks(0, 2, []),
+            other(2, 3, []),
+            backticks(3, 4, []),
+        ]
+    };
+}
+
+#[test]
+fn test_autolink() {
+    parses_to! {
+        parser: InlineParser,
+        input: "<a+b:c\\d><foo@bar.baz>",
+        rule: Rule::inline,
+        tokens: [
+            uri_autolink(0, 9, []),
+            email_autolink(9, 22, []),
+        ]
+    };
+
+    parses_to! {
+        parser: InlineParser,
+        input: "<m:a>",
+        rule: Rule::inline,
+        tokens: [
+            other(0, 1, []),
+            other(1, 2, []),
+            other(2, 3, []),
+            other(3, 4, []),
+            other(4, 5, []),
+        ]
+    };
+}
+
+#[test]
+fn test_html_inline() {
+    parses_to! {
+        parser: InlineParser,
+        input: "<a href='x'\n/></a><!---->",
+        rule: Rule::inline,
+        tokens: [
+            html_inline(0, 14, []),
+            html_inline(14, 18, []),
+            html_inline(18, 25, []),
+        ]
+    };
+
+    parses_to! {
+        parser: InlineParser,
+        input: "</a b>",
+        rule: Rule::inline,
+        tokens: [
+            other(0, 1, []),
+            other(1, 2, []),
+            other(2, 3, []),
+            other(3, 4, []),
+            other(4, 5, []),
+            other(5, 6, []),
+        ]
+    };
+}
+
+#[test]
+fn test_delimiter_run() {
+    parses_to! {
+        parser: InlineParser,
+        input: "foo**_bar*",
+        rule: Rule::inline,
+        tokens: [
+            other(0, 1, []),
+            other(1, 2, []),
+            other(2, 3, []),
+            delimiter_run(3, 5, []),
+            delimiter_run(5, 6, []),
+            other(6, 7, []),
+            other(7, 8, []),
+            other(8, 9, []),
+            delimiter_run(9, 10, []),
         ]
     };
 }
@@ -1,75 +1,82 @@
 use block::Block;
 use block::BlockType;
+use block_parser::normalize_label;
+use error::{Error, Result};
+use inline::Inline;
+use options::ParseOptions;
+use std::collections::HashMap;
 
 mod interpreter;
 mod lexer;
+mod punctuation;
 
-pub fn top(block_tree: &mut Block) {
-    match block_tree {
-        Block {
-            block_type: BlockType::Document,
-            children,
-            ..
-        } => {
-            for child in children {
-                top(child);
-            }
-        }
-        Block {
-            block_type: BlockType::Paragraph,
-            raw_text,
-            ..
-        }
-        | Block {
-            block_type: BlockType::AtxHeading1,
-            raw_text,
-            ..
-        }
-        | Block {
-            block_type: BlockType::AtxHeading2,
-            raw_text,
-            ..
-        }
-        | Block {
-            block_type: BlockType::AtxHeading3,
-            raw_text,
-            ..
-        }
-        | Block {
-            block_type: BlockType::AtxHeading4,
-            raw_text,
-            ..
-        }
-        | Block {
-            block_type: BlockType::AtxHeading5,
-            raw_text,
-            ..
-        }
-        | Block {
-            block_type: BlockType::AtxHeading6,
-            raw_text,
-            ..
-        }
-        | Block {
-            block_type: BlockType::SetextHeadingUnderline1,
-            raw_text,
-            ..
+// Link reference definitions by normalized label, as (destination, title).
+pub type Definitions = HashMap<String, (String, String)>;
+
+// Collects the link reference definitions of a block tree. Only the first
+// definition of a label counts.
+fn collect_definitions(block: &Block, definitions: &mut Definitions) {
+    if let BlockType::LinkDefinition {
+        ref label,
+        ref destination,
+        ref title,
+    } = block.block_type
+    {
+        definitions
+            .entry(normalize_label(label))
+            .or_insert_with(|| (destination.clone(), title.clone()));
+    }
+    for child in block.children.iter() {
+        collect_definitions(child, definitions);
+    }
+}
+
+// References may come before their definitions, so all definitions are
+// collected before any inlines are parsed.
+pub fn top(block_tree: &mut Block, options: &ParseOptions) -> Result<()> {
+    let mut definitions = HashMap::new();
+    collect_definitions(block_tree, &mut definitions);
+    parse_inlines(block_tree, options, &definitions)
+}
+
+fn parse_inlines(
+    block_tree: &mut Block,
+    options: &ParseOptions,
+    definitions: &Definitions,
+) -> Result<()> {
+    match block_tree.block_type {
+        BlockType::Paragraph | BlockType::Heading { .. } => {
+            block_tree.inlines = parse_leaf(block_tree, options, definitions)?;
         }
-        | Block {
-            block_type: BlockType::SetextHeadingUnderline2,
-            raw_text,
-            ..
-        } => to_html(raw_text),
-        Block { .. } => {}
+        _ => (),
     }
+
+    for child in block_tree.children.iter_mut() {
+        parse_inlines(child, options, definitions)?;
+    }
+    Ok(())
 }
 
-fn to_html(raw_text: &mut String) {
-    let cloned = raw_text.to_string();
-    let html = match lexer::lex(&cloned) {
-        Ok(tokens) => interpreter::top(tokens),
-        _ => cloned.to_string(),
-    };
-    raw_text.clear();
-    raw_text.push_str(&html.trim_right()); //TODO
+// Parses the text of a paragraph or heading into inlines.
+fn parse_leaf(
+    block: &Block,
+    options: &ParseOptions,
+    definitions: &Definitions,
+) -> Result<Vec<Inline>> {
+    let text = block.raw_text.trim_end();
+    lexer::lex(text)
+        .map_err(|e| Error::inline(&e))
+        .and_then(|tokens| interpreter::top(text, tokens, options, definitions))
+        .map_err(|e| in_document(e, block))
+}
+
+// Moves an error from the line of the block's text it is on to the line of
+// the document.
+fn in_document(error: Error, block: &Block) -> Error {
+    match error {
+        Error::Inline { line, column } => Error::Inline {
+            line: block.position.start.line + line - 1,
+            column,
+        },
+    }
 }
@@ -1,53 +1,182 @@
-#![feature(test)]
+#![cfg_attr(test, feature(test))]
 
 use std::ffi::CStr;
 use std::ffi::CString;
 use std::mem;
 use std::os::raw::{c_char, c_void};
 
-#[macro_use]
 extern crate pest;
 #[macro_use]
 extern crate pest_derive;
-extern crate htmlescape;
 
+#[cfg(test)]
 extern crate test;
 
-mod block;
+pub mod block;
 mod block_parser;
+mod entity;
+mod error;
+pub mod event;
 mod html_translator;
+pub mod inline;
 mod inline_parser;
+mod options;
+pub mod visit;
 
 #[cfg(test)]
 mod bench;
 #[cfg(test)]
 mod tests;
 
+pub use block::{Block, BlockType, Point, Position};
+pub use error::{Error, Result};
+pub use event::{Event, Parser, Tag};
+pub use html_translator::{push_html, render_to, render_to_io};
+pub use inline::Inline;
+pub use options::{ParseOptions, RenderOptions};
+pub use visit::{Visitor, VisitorMut};
+
+/// Renders a Markdown document to HTML.
+///
+/// ```
+/// assert_eq!(markdown::to_html("# foo").unwrap(), "<h1>foo</h1>");
+/// ```
+pub fn to_html(input: &str) -> Result<String> {
+    html_translator::top(input)
+}
+
+/// Renders a Markdown document to HTML with the given options.
+///
+/// ```
+/// let render_options = markdown::RenderOptions {
+///     sourcepos: true,
+///     ..markdown::RenderOptions::default()
+/// };
+/// assert_eq!(
+///     markdown::to_html_with_options("# foo", &Default::default(), &render_options).unwrap(),
+///     "<h1 data-sourcepos=\"1:1-1:5\">foo</h1>"
+/// );
+/// ```
+pub fn to_html_with_options(
+    input: &str,
+    parse_options: &ParseOptions,
+    render_options: &RenderOptions,
+) -> Result<String> {
+    html_translator::top_with_options(input, parse_options, render_options)
+}
+
+/// Renders a Markdown document to HTML, letting `transform` change the
+/// block tree in between.
+///
+/// See `VisitorMut` for an example.
+pub fn to_html_with_transform<V: VisitorMut>(
+    input: &str,
+    parse_options: &ParseOptions,
+    render_options: &RenderOptions,
+    transform: &mut V,
+) -> Result<String> {
+    let mut block_tree = parse_with_options(input, parse_options)?;
+    transform.visit_block_mut(&mut block_tree);
+    Ok(html_translator::render(block_tree, render_options))
+}
+
+/// Parses a Markdown document into its block tree.
+///
+/// Leaf blocks keep their Markdown source in `raw_text` and their parsed
+/// content in `inlines`.
+///
+/// ```
+/// use markdown::Inline;
+///
+/// let document = markdown::parse("*foo*").unwrap();
+/// assert_eq!(
+///     document.children[0].inlines,
+///     vec![Inline::Emphasis(vec![Inline::Text("foo".to_string())])]
+/// );
+/// ```
+pub fn parse(input: &str) -> Result<Block> {
+    parse_with_options(input, &ParseOptions::default())
+}
+
+/// Parses a Markdown document into its block tree with the given options.
+pub fn parse_with_options(input: &str, options: &ParseOptions) -> Result<Block> {
+    let mut block_tree = block_parser::top(input, options)?;
+    inline_parser::top(&mut block_tree, options)?;
+    Ok(block_tree)
+}
+
 #[no_mangle]
 pub extern "C" fn alloc(size: usize) -> *mut c_void {
     let mut buf = Vec::with_capacity(size);
     let ptr = buf.as_mut_ptr();
     mem::forget(buf);
-    return ptr as *mut c_void;
+    ptr as *mut c_void
 }
 
+/// Frees a buffer returned by `alloc`.
+///
+/// # Safety
+///
+/// `ptr` must come from `alloc` called with `cap`, and must not be used
+/// afterwards.
 #[no_mangle]
-pub extern "C" fn dealloc(ptr: *mut c_void, cap: usize) {
-    unsafe {
-        let _buf = Vec::from_raw_parts(ptr, 0, cap);
-    }
+pub unsafe extern "C" fn dealloc(ptr: *mut c_void, cap: usize) {
+    let _buf = Vec::from_raw_parts(ptr, 0, cap);
 }
 
+/// Frees a string returned by `translate`.
+///
+/// # Safety
+///
+/// `ptr` must come from `translate` or `translate_with_sourcepos`, and must
+/// not be used afterwards.
 #[no_mangle]
-pub extern "C" fn dealloc_str(ptr: *mut c_char) {
-    unsafe {
-        let _ = CString::from_raw(ptr);
-    }
+pub unsafe extern "C" fn dealloc_str(ptr: *mut c_char) {
+    let _ = CString::from_raw(ptr);
 }
 
+/// Renders the nul-terminated Markdown at `data` to a nul-terminated HTML
+/// string, which is freed with `dealloc_str`.
+///
+/// # Safety
+///
+/// `data` must point to a nul-terminated string that stays valid and
+/// unchanged for the duration of the call.
 #[no_mangle]
-pub extern "C" fn translate(data: *mut c_char) -> *mut c_char {
-    let input = unsafe { CStr::from_ptr(data).to_string_lossy().into_owned() };
-    let output = html_translator::top(&input);
-    CString::new(output).unwrap().into_raw()
+pub unsafe extern "C" fn translate(data: *mut c_char) -> *mut c_char {
+    translate_with_options(data, &RenderOptions::default())
+}
+
+/// Like `translate`, with `data-sourcepos` attributes on block-level
+/// elements.
+///
+/// # Safety
+///
+/// `data` must point to a nul-terminated string that stays valid and
+/// unchanged for the duration of the call.
+#[no_mangle]
+pub unsafe extern "C" fn translate_with_sourcepos(data: *mut c_char) -> *mut c_char {
+    let options = RenderOptions {
+        sourcepos: true,
+        ..RenderOptions::default()
+    };
+    translate_with_options(data, &options)
+}
+
+unsafe fn translate_with_options(data: *mut c_char, options: &RenderOptions) -> *mut c_char {
+    let input = CStr::from_ptr(data).to_string_lossy().into_owned();
+    let output = match to_html_with_options(&input, &ParseOptions::default(), options) {
+        Ok(html) => html,
+        Err(e) => {
+            let mut html = "<pre>".to_string();
+            // Writing to a String never fails.
+            let _ = html_translator::escape_html(&mut html, &e.to_string());
+            html.push_str("</pre>");
+            html
+        }
+    };
+    // The output can not contain an interior nul byte once they are replaced.
+    CString::new(output.replace('\0', "\u{FFFD}"))
+        .unwrap_or_default()
+        .into_raw()
 }
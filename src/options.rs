@@ -0,0 +1,55 @@
+/// Options for turning Markdown into a block tree.
+///
+/// The default parses CommonMark without extensions.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ParseOptions {
+    /// GFM strikethrough: `~~text~~` becomes `<del>text</del>`.
+    pub strikethrough: bool,
+    /// GFM task list items: `- [ ] foo` and `- [x] foo` get a checkbox.
+    pub tasklist: bool,
+}
+
+/// Options for turning a block tree into HTML.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RenderOptions {
+    /// Emit `data-sourcepos="startline:col-endline:col"` on block-level
+    /// elements, like cmark's `--sourcepos`.
+    pub sourcepos: bool,
+    /// Escape raw HTML instead of passing it through, and leave out
+    /// `javascript:`, `vbscript:`, `file:` and non-image `data:` link and
+    /// image destinations.
+    pub safe: bool,
+    /// Close void elements XHTML style (`<br />`) rather than HTML5 (`<br>`).
+    pub xhtml: bool,
+    /// Render soft line breaks as hard line breaks.
+    pub hard_breaks: bool,
+    /// Added to every heading level; the result is capped at 6.
+    pub heading_offset: u8,
+}
+
+impl Default for RenderOptions {
+    fn default() -> RenderOptions {
+        RenderOptions {
+            sourcepos: false,
+            safe: false,
+            xhtml: true,
+            hard_breaks: false,
+            heading_offset: 0,
+        }
+    }
+}
+
+impl RenderOptions {
+    // The end of a void element such as `<hr />` or `<br>`.
+    pub fn void_end(&self) -> &'static str {
+        if self.xhtml {
+            " />"
+        } else {
+            ">"
+        }
+    }
+
+    pub fn heading_level(&self, level: u8) -> u8 {
+        level.saturating_add(self.heading_offset).min(6)
+    }
+}
@@ -0,0 +1,311 @@
+use block::{Block, BlockType};
+use inline::Inline;
+
+/// Walks a block tree, with a hook for every kind of block.
+///
+/// Each hook visits the children and inlines of its block by default, so an
+/// implementation only overrides the hooks it needs and calls `walk_block`
+/// to keep descending.
+///
+/// ```
+/// use markdown::{Block, Visitor};
+///
+/// struct HeadingCount(usize);
+///
+/// impl Visitor for HeadingCount {
+///     fn visit_heading(&mut self, _block: &Block) {
+///         self.0 += 1;
+///     }
+/// }
+///
+/// let document = markdown::parse("# foo\n\n> ## bar").unwrap();
+/// let mut count = HeadingCount(0);
+/// count.visit_block(&document);
+/// assert_eq!(count.0, 2);
+/// ```
+pub trait Visitor {
+    fn visit_block(&mut self, block: &Block) {
+        match block.block_type {
+            BlockType::Document => self.visit_document(block),
+            BlockType::ThematicBreaks => self.visit_thematic_break(block),
+            BlockType::BreakLine => self.visit_break_line(block),
+            BlockType::Heading { .. } => self.visit_heading(block),
+            BlockType::IndentedCodeBlock => self.visit_indented_code_block(block),
+            BlockType::FencedCodeBlock { .. } => self.visit_fenced_code_block(block),
+            BlockType::BlockQuote => self.visit_block_quote(block),
+            BlockType::Paragraph => self.visit_paragraph(block),
+            BlockType::HtmlBlock => self.visit_html_block(block),
+            BlockType::BulletList { .. } => self.visit_bullet_list(block),
+            BlockType::OrderedList { .. } => self.visit_ordered_list(block),
+            BlockType::BulletListItem { .. } => self.visit_bullet_list_item(block),
+            BlockType::OrderedListItem { .. } => self.visit_ordered_list_item(block),
+            BlockType::LinkDefinition { .. } => self.visit_link_definition(block),
+        }
+    }
+
+    fn visit_document(&mut self, block: &Block) {
+        walk_block(self, block)
+    }
+
+    fn visit_thematic_break(&mut self, block: &Block) {
+        walk_block(self, block)
+    }
+
+    fn visit_break_line(&mut self, block: &Block) {
+        walk_block(self, block)
+    }
+
+    fn visit_heading(&mut self, block: &Block) {
+        walk_block(self, block)
+    }
+
+    fn visit_indented_code_block(&mut self, block: &Block) {
+        walk_block(self, block)
+    }
+
+    fn visit_fenced_code_block(&mut self, block: &Block) {
+        walk_block(self, block)
+    }
+
+    fn visit_block_quote(&mut self, block: &Block) {
+        walk_block(self, block)
+    }
+
+    fn visit_paragraph(&mut self, block: &Block) {
+        walk_block(self, block)
+    }
+
+    fn visit_html_block(&mut self, block: &Block) {
+        walk_block(self, block)
+    }
+
+    fn visit_bullet_list(&mut self, block: &Block) {
+        walk_block(self, block)
+    }
+
+    fn visit_ordered_list(&mut self, block: &Block) {
+        walk_block(self, block)
+    }
+
+    fn visit_bullet_list_item(&mut self, block: &Block) {
+        walk_block(self, block)
+    }
+
+    fn visit_ordered_list_item(&mut self, block: &Block) {
+        walk_block(self, block)
+    }
+
+    fn visit_link_definition(&mut self, block: &Block) {
+        walk_block(self, block)
+    }
+
+    fn visit_inline(&mut self, inline: &Inline) {
+        walk_inline(self, inline)
+    }
+}
+
+/// Visits the inlines and then the children of a block.
+pub fn walk_block<V: Visitor + ?Sized>(visitor: &mut V, block: &Block) {
+    for inline in &block.inlines {
+        visitor.visit_inline(inline);
+    }
+    for child in &block.children {
+        visitor.visit_block(child);
+    }
+}
+
+/// Visits the children of an inline such as a link or emphasis.
+pub fn walk_inline<V: Visitor + ?Sized>(visitor: &mut V, inline: &Inline) {
+    match *inline {
+        Inline::Emphasis(ref children)
+        | Inline::Strong(ref children)
+        | Inline::Strikethrough(ref children)
+        | Inline::Link { ref children, .. }
+        | Inline::Image { ref children, .. } => {
+            for child in children {
+                visitor.visit_inline(child);
+            }
+        }
+        _ => (),
+    }
+}
+
+/// Like `Visitor`, but able to change the tree, e.g. to rewrite link
+/// destinations or drop blocks.
+///
+/// ```
+/// use markdown::{Block, BlockType, VisitorMut};
+///
+/// // Removes every block quote.
+/// struct StripQuotes;
+///
+/// impl VisitorMut for StripQuotes {
+///     fn visit_document_mut(&mut self, block: &mut Block) {
+///         block.children.retain(|child| child.block_type != BlockType::BlockQuote);
+///         markdown::visit::walk_block_mut(self, block)
+///     }
+/// }
+///
+/// let html = markdown::to_html_with_transform(
+///     "foo\n\n> bar",
+///     &Default::default(),
+///     &Default::default(),
+///     &mut StripQuotes,
+/// );
+/// assert_eq!(html.unwrap(), "<p>foo</p>");
+/// ```
+pub trait VisitorMut {
+    fn visit_block_mut(&mut self, block: &mut Block) {
+        match block.block_type {
+            BlockType::Document => self.visit_document_mut(block),
+            BlockType::ThematicBreaks => self.visit_thematic_break_mut(block),
+            BlockType::BreakLine => self.visit_break_line_mut(block),
+            BlockType::Heading { .. } => self.visit_heading_mut(block),
+            BlockType::IndentedCodeBlock => self.visit_indented_code_block_mut(block),
+            BlockType::FencedCodeBlock { .. } => self.visit_fenced_code_block_mut(block),
+            BlockType::BlockQuote => self.visit_block_quote_mut(block),
+            BlockType::Paragraph => self.visit_paragraph_mut(block),
+            BlockType::HtmlBlock => self.visit_html_block_mut(block),
+            BlockType::BulletList { .. } => self.visit_bullet_list_mut(block),
+            BlockType::OrderedList { .. } => self.visit_ordered_list_mut(block),
+            BlockType::BulletListItem { .. } => self.visit_bullet_list_item_mut(block),
+            BlockType::OrderedListItem { .. } => self.visit_ordered_list_item_mut(block),
+            BlockType::LinkDefinition { .. } => self.visit_link_definition_mut(block),
+        }
+    }
+
+    fn visit_document_mut(&mut self, block: &mut Block) {
+        walk_block_mut(self, block)
+    }
+
+    fn visit_thematic_break_mut(&mut self, block: &mut Block) {
+        walk_block_mut(self, block)
+    }
+
+    fn visit_break_line_mut(&mut self, block: &mut Block) {
+        walk_block_mut(self, block)
+    }
+
+    fn visit_heading_mut(&mut self, block: &mut Block) {
+        walk_block_mut(self, block)
+    }
+
+    fn visit_indented_code_block_mut(&mut self, block: &mut Block) {
+        walk_block_mut(self, block)
+    }
+
+    fn visit_fenced_code_block_mut(&mut self, block: &mut Block) {
+        walk_block_mut(self, block)
+    }
+
+    fn visit_block_quote_mut(&mut self, block: &mut Block) {
+        walk_block_mut(self, block)
+    }
+
+    fn visit_paragraph_mut(&mut self, block: &mut Block) {
+        walk_block_mut(self, block)
+    }
+
+    fn visit_html_block_mut(&mut self, block: &mut Block) {
+        walk_block_mut(self, block)
+    }
+
+    fn visit_bullet_list_mut(&mut self, block: &mut Block) {
+        walk_block_mut(self, block)
+    }
+
+    fn visit_ordered_list_mut(&mut self, block: &mut Block) {
+        walk_block_mut(self, block)
+    }
+
+    fn visit_bullet_list_item_mut(&mut self, block: &mut Block) {
+        walk_block_mut(self, block)
+    }
+
+    fn visit_ordered_list_item_mut(&mut self, block: &mut Block) {
+        walk_block_mut(self, block)
+    }
+
+    fn visit_link_definition_mut(&mut self, block: &mut Block) {
+        walk_block_mut(self, block)
+    }
+
+    fn visit_inline_mut(&mut self, inline: &mut Inline) {
+        walk_inline_mut(self, inline)
+    }
+}
+
+/// Visits the inlines and then the children of a block.
+pub fn walk_block_mut<V: VisitorMut + ?Sized>(visitor: &mut V, block: &mut Block) {
+    for inline in block.inlines.iter_mut() {
+        visitor.visit_inline_mut(inline);
+    }
+    for child in block.children.iter_mut() {
+        visitor.visit_block_mut(child);
+    }
+}
+
+/// Visits the children of an inline such as a link or emphasis.
+pub fn walk_inline_mut<V: VisitorMut + ?Sized>(visitor: &mut V, inline: &mut Inline) {
+    match *inline {
+        Inline::Emphasis(ref mut children)
+        | Inline::Strong(ref mut children)
+        | Inline::Strikethrough(ref mut children)
+        | Inline::Link {
+            ref mut children, ..
+        }
+        | Inline::Image {
+            ref mut children, ..
+        } => {
+            for child in children.iter_mut() {
+                visitor.visit_inline_mut(child);
+            }
+        }
+        _ => (),
+    }
+}
+
+#[test]
+fn test_visitor_mut() {
+    // Numbers headings and upper-cases their text.
+    struct Headings(usize);
+
+    impl VisitorMut for Headings {
+        fn visit_heading_mut(&mut self, block: &mut Block) {
+            self.0 += 1;
+            block
+                .inlines
+                .insert(0, Inline::Text(format!("{}. ", self.0)));
+            walk_block_mut(self, block)
+        }
+
+        fn visit_inline_mut(&mut self, inline: &mut Inline) {
+            if let Inline::Text(ref mut s) = *inline {
+                *s = s.to_uppercase();
+            }
+            walk_inline_mut(self, inline)
+        }
+    }
+
+    let mut document = ::parse("# foo\n\n## *bar*").unwrap();
+    Headings(0).visit_block_mut(&mut document);
+    let inlines: Vec<_> = document
+        .children
+        .iter()
+        .map(|child| &child.inlines)
+        .filter(|inlines| !inlines.is_empty())
+        .collect();
+    assert_eq!(
+        inlines,
+        vec![
+            &vec![
+                Inline::Text("1. ".to_string()),
+                Inline::Text("FOO".to_string())
+            ],
+            &vec![
+                Inline::Text("2. ".to_string()),
+                Inline::Emphasis(vec![Inline::Text("BAR".to_string())]),
+            ],
+        ]
+    );
+}